@@ -0,0 +1,97 @@
+use std::fs;
+use std::io;
+
+use crate::error::{AocError, Result};
+use crate::toml::parse_tables;
+
+/// A known answer: the answer to `part` for the input hashing to `input`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Answer {
+    pub part: u8,
+    pub input: String,
+    pub answer: String,
+}
+
+/// The accepted answers of a day, stored as TOML next to its input:
+///
+/// ```toml
+/// [[answer]]
+/// part = 1
+/// input = "a1b2c3d4e5f60718"
+/// answer = "390923"
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers {
+    answers: Vec<Answer>,
+}
+
+/// A stable hash of the puzzle input (64-bit FNV-1a, in hex), so answers
+/// are only compared against runs on the same input.
+pub fn input_hash(input: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in input.bytes() {
+        hash = (hash ^ byte as u64).wrapping_mul(0x100000001b3);
+    }
+    return format!("{:016x}", hash);
+}
+
+impl Answers {
+    /// Loads `filename`; a missing file has no answers.
+    pub fn load(filename: &str) -> Result<Answers> {
+        let contents = match fs::read_to_string(filename) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Answers::default()),
+            Err(e) => return Err(AocError::Io { file: filename.to_string(), source: e }),
+        };
+        return Answers::parse(&contents).map_err(|e| e.with_file(filename));
+    }
+
+    fn parse(contents: &str) -> Result<Answers> {
+        let mut answers: Vec<Answer> = Vec::new();
+        for table in parse_tables(contents, "answer")? {
+            table.check_keys(&["part", "input", "answer"])?;
+            let part = table.integer("part")?.ok_or(table.missing("part"))?;
+            answers.push(Answer {
+                part: u8::try_from(part).map_err(|_| AocError::parse(table.line, "part", "invalid part"))?,
+                input: table.string("input")?.ok_or(table.missing("input"))?,
+                answer: table.string("answer")?.ok_or(table.missing("answer"))?,
+            });
+        }
+        return Ok(Answers { answers: answers });
+    }
+
+    pub fn get(&self, part: u8, input: &str) -> Option<&str> {
+        return self.answers
+            .iter()
+            .find(|a| a.part == part && a.input == input)
+            .map(|a| a.answer.as_str());
+    }
+
+    /// Records `answer`, replacing any previous answer for the same part and
+    /// input.
+    pub fn set(&mut self, part: u8, input: &str, answer: &str) {
+        match self.answers.iter_mut().find(|a| a.part == part && a.input == input) {
+            Some(known) => known.answer = answer.to_string(),
+            None => self.answers.push(Answer {
+                part: part,
+                input: input.to_string(),
+                answer: answer.to_string(),
+            }),
+        }
+    }
+
+    pub fn save(&self, filename: &str) -> Result<()> {
+        let mut contents = String::new();
+        for (i, answer) in self.answers.iter().enumerate() {
+            if i > 0 {
+                contents.push('\n');
+            }
+            contents.push_str("[[answer]]\n");
+            contents.push_str(&format!("part = {}\n", answer.part));
+            contents.push_str(&format!("input = \"{}\"\n", answer.input));
+            contents.push_str(&format!("answer = \"{}\"\n", answer.answer));
+        }
+        return fs::write(filename, contents)
+            .map_err(|e| AocError::Io { file: filename.to_string(), source: e });
+    }
+}
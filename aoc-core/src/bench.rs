@@ -0,0 +1,153 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::error::{AocError, Result};
+use crate::json::Json;
+use crate::solution::Solution;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// The system allocator, counting every allocation. Binaries that want
+/// allocation counts in their measurements install it with
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator;
+/// ```
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        return unsafe { System.alloc(layout) };
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        return unsafe { System.alloc_zeroed(layout) };
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        return unsafe { System.realloc(ptr, layout, new_size) };
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+    }
+}
+
+/// Allocations made so far through `CountingAllocator`; always 0 if it is
+/// not the global allocator.
+pub fn allocations() -> u64 {
+    return ALLOCATIONS.load(Ordering::Relaxed);
+}
+
+/// Timings of repeated runs of one part of a day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub day: u8,
+    pub part: u8,
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+    /// Allocations of a single run.
+    pub allocations: u64,
+}
+
+/// Runs `part` of `solution` on `input` `iterations` times.
+pub fn measure(solution: &dyn Solution, part: u8, input: &str, iterations: usize) -> Result<Measurement> {
+    let mut times: Vec<Duration> = Vec::new();
+    let mut allocation_counts: Vec<u64> = Vec::new();
+
+    for _ in 0..iterations.max(1) {
+        let allocations_before = allocations();
+        let start = Instant::now();
+        match part {
+            1 => solution.part1(input)?,
+            _ => solution.part2(input)?,
+        };
+        times.push(start.elapsed());
+        allocation_counts.push(allocations() - allocations_before);
+    }
+    times.sort();
+    allocation_counts.sort();
+
+    return Ok(Measurement {
+        day: solution.day(),
+        part: part,
+        iterations: times.len(),
+        min: times[0],
+        median: times[times.len() / 2],
+        max: times[times.len() - 1],
+        allocations: allocation_counts[allocation_counts.len() / 2],
+    });
+}
+
+impl Measurement {
+    pub fn to_json(&self) -> Json {
+        return Json::object(vec![
+            ("day", Json::from(self.day as u64)),
+            ("part", Json::from(self.part as u64)),
+            ("iterations", Json::from(self.iterations as u64)),
+            ("min_ns", Json::from(self.min.as_nanos() as u64)),
+            ("median_ns", Json::from(self.median.as_nanos() as u64)),
+            ("max_ns", Json::from(self.max.as_nanos() as u64)),
+            ("allocations", Json::from(self.allocations)),
+        ]);
+    }
+
+    pub fn from_json(json: &Json) -> Result<Measurement> {
+        let field = |key: &str| -> Result<u64> {
+            return json.get(key)
+                .and_then(|value| value.as_f64())
+                .map(|value| value as u64)
+                .ok_or(AocError::parse(1, key, "missing or invalid field in benchmark results"));
+        };
+        return Ok(Measurement {
+            day: field("day")? as u8,
+            part: field("part")? as u8,
+            iterations: field("iterations")? as usize,
+            min: Duration::from_nanos(field("min_ns")?),
+            median: Duration::from_nanos(field("median_ns")?),
+            max: Duration::from_nanos(field("max_ns")?),
+            allocations: field("allocations")?,
+        });
+    }
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "day {} part {}: min {:.3?}  median {:.3?}  max {:.3?}  ({} runs, {} allocations)",
+            self.day, self.part, self.min, self.median, self.max, self.iterations, self.allocations
+        )
+    }
+}
+
+/// A set of measurements as saved to disk: `{"results": [...]}`.
+pub fn results_to_json(measurements: &[Measurement]) -> Json {
+    let results = measurements.iter().map(|m| m.to_json()).collect();
+    return Json::object(vec![("results", Json::Array(results))]);
+}
+
+pub fn results_from_json(json: &Json) -> Result<Vec<Measurement>> {
+    let results = json.get("results")
+        .and_then(|results| results.as_array())
+        .ok_or(AocError::parse(1, "results", "expected a list of benchmark results"))?;
+    return results.iter().map(Measurement::from_json).collect();
+}
+
+/// How much slower (positive) or faster (negative) the median of `current`
+/// is than that of `baseline`, in percent.
+pub fn median_change(current: &Measurement, baseline: &Measurement) -> f64 {
+    let baseline_ns = baseline.median.as_nanos() as f64;
+    if baseline_ns == 0.0 {
+        return 0.0;
+    }
+    return (current.median.as_nanos() as f64 - baseline_ns) / baseline_ns * 100.0;
+}
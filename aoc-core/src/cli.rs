@@ -0,0 +1,130 @@
+use std::process;
+
+use crate::error::{AocError, Result};
+use crate::input::read_input;
+use crate::log;
+use crate::output::{run_part, Format};
+use crate::solution::Solution;
+
+/// A small command line parser: options are pulled out by name and whatever
+/// is left over is reported by `finish`.
+pub struct Args {
+    args: Vec<String>,
+}
+
+impl Args {
+    pub fn from_env() -> Args {
+        return Args::new(std::env::args().skip(1).collect());
+    }
+
+    pub fn new(args: Vec<String>) -> Args {
+        return Args { args: args };
+    }
+
+    /// Takes the first argument if it is not an option, e.g. `run` in
+    /// `aoc run --day 1`.
+    pub fn subcommand(&mut self) -> Option<String> {
+        if self.args.first().is_some_and(|arg| !arg.starts_with('-')) {
+            return Some(self.args.remove(0));
+        }
+        return None;
+    }
+
+    /// Takes `name` if present.
+    pub fn flag(&mut self, name: &str) -> bool {
+        match self.args.iter().position(|arg| arg == name) {
+            Some(idx) => {
+                self.args.remove(idx);
+                return true;
+            },
+            None => return false,
+        }
+    }
+
+    /// Takes `name VALUE` or `name=VALUE`.
+    pub fn value(&mut self, name: &str) -> Result<Option<String>> {
+        let prefix = format!("{}=", name);
+        for idx in 0..self.args.len() {
+            if self.args[idx] == name {
+                if idx + 1 >= self.args.len() {
+                    return Err(AocError::Usage(format!("missing value for {}", name)));
+                }
+                self.args.remove(idx);
+                return Ok(Some(self.args.remove(idx)));
+            }
+            if let Some(value) = self.args[idx].strip_prefix(&prefix) {
+                let value = value.to_string();
+                self.args.remove(idx);
+                return Ok(Some(value));
+            }
+        }
+        return Ok(None);
+    }
+
+    /// Takes the puzzle input path, the first argument that is not an option
+    /// (`-` meaning standard input), falling back to `default`. Options with
+    /// a value must be taken before this.
+    pub fn input(&mut self, default: &str) -> String {
+        match self.args.iter().position(|arg| arg == "-" || !arg.starts_with('-')) {
+            Some(idx) => return self.args.remove(idx),
+            None => return default.to_string(),
+        }
+    }
+
+    /// Fails if any argument was not taken.
+    pub fn finish(self) -> Result<()> {
+        match self.args.first() {
+            Some(arg) => return Err(AocError::Usage(format!("unexpected argument {}", arg))),
+            None => return Ok(()),
+        }
+    }
+}
+
+/// Reports `error` on a single line and exits: with status 2 for command
+/// line errors and 1 for anything else.
+pub fn exit_with_error(error: &AocError) -> ! {
+    match error {
+        AocError::Usage(_) => {
+            eprintln!("{}", error);
+            process::exit(2);
+        },
+        _ => {
+            eprintln!("error: {}", error);
+            process::exit(1);
+        },
+    }
+}
+
+/// Reports every error in `errors`, one per line, and exits like
+/// `exit_with_error` does for the last one.
+pub fn exit_with_errors(errors: &[AocError]) -> ! {
+    let (last, rest) = errors.split_last().expect("at least one error to report");
+    for error in rest {
+        eprintln!("error: {}", error);
+    }
+    exit_with_error(last);
+}
+
+/// Prints the answers to both parts of `solution` for the input in
+/// `filename`.
+pub fn print_answers(solution: &dyn Solution, filename: &str, format: Format) -> Result<()> {
+    let input = read_input(filename)?;
+    for part in [1, 2] {
+        run_part(solution, part, &input)?.print(format);
+    }
+    return Ok(());
+}
+
+/// The whole `main` of a day without options of its own:
+/// `dayN [--format text|json] [-v|-q] [PATH|-]`.
+pub fn run_day(solution: &dyn Solution, default_input: &str) {
+    let mut args = Args::from_env();
+    log::init(&mut args).unwrap_or_else(|e| exit_with_error(&e));
+    let format = Format::from_args(&mut args).unwrap_or_else(|e| exit_with_error(&e));
+    let filename = args.input(default_input);
+    args.finish().unwrap_or_else(|e| exit_with_error(&e));
+
+    if let Err(error) = print_answers(solution, &filename, format) {
+        exit_with_error(&error.with_file(&filename));
+    }
+}
@@ -0,0 +1,112 @@
+use std::fmt;
+use std::io;
+
+/// Everything that can go wrong while solving a puzzle.
+#[derive(Debug)]
+pub enum AocError {
+    /// A line of the puzzle input is malformed. `line` and `column`, when
+    /// known, start at 1 and `text` is the offending part of the line.
+    Parse {
+        file: Option<String>,
+        line: usize,
+        column: Option<usize>,
+        text: String,
+        reason: String,
+    },
+    /// The puzzle input could not be read.
+    Io { file: String, source: io::Error },
+    /// The input is well formed but has no answer, e.g. no bingo board wins.
+    NoSolution(String),
+    /// The command line is wrong.
+    Usage(String),
+}
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+impl AocError {
+    pub fn parse(line: usize, text: &str, reason: &str) -> AocError {
+        return AocError::Parse {
+            file: None,
+            line: line,
+            column: None,
+            text: text.to_string(),
+            reason: reason.to_string(),
+        };
+    }
+
+    pub fn no_solution(reason: &str) -> AocError {
+        return AocError::NoSolution(reason.to_string());
+    }
+
+    pub fn usage(reason: &str) -> AocError {
+        return AocError::Usage(reason.to_string());
+    }
+
+    /// Names the input file a parse error comes from, unless it already has
+    /// one. `-` is shown as `<stdin>`.
+    pub fn with_file(self, filename: &str) -> AocError {
+        match self {
+            AocError::Parse { file: None, line, column, text, reason } => {
+                let filename = if filename == "-" { "<stdin>" } else { filename };
+                return AocError::Parse {
+                    file: Some(filename.to_string()),
+                    line: line,
+                    column: column,
+                    text: text,
+                    reason: reason,
+                };
+            },
+            _ => return self,
+        }
+    }
+
+    /// Moves a parse error `offset` lines down, for errors found in part of
+    /// the input, such as the lines after a header.
+    pub fn offset_lines(self, offset: usize) -> AocError {
+        match self {
+            AocError::Parse { file, line, column, text, reason } => {
+                return AocError::Parse { file: file, line: line + offset, column: column, text: text, reason: reason };
+            },
+            _ => return self,
+        }
+    }
+
+    /// Points a parse error at the column of its line where `text` starts.
+    pub fn at_column(self, column: usize) -> AocError {
+        match self {
+            AocError::Parse { file, line, text, reason, .. } => {
+                return AocError::Parse { file: file, line: line, column: Some(column), text: text, reason: reason };
+            },
+            _ => return self,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AocError::Parse { file, line, column, text, reason } => {
+                if let Some(file) = file {
+                    write!(f, "{}:", file)?;
+                }
+                write!(f, "{}:", line)?;
+                if let Some(column) = column {
+                    write!(f, "{}:", column)?;
+                }
+                write!(f, " {}: {:?}", reason, text)
+            },
+            AocError::Io { file, source } => write!(f, "{}: {}", file, source),
+            AocError::NoSolution(reason) => write!(f, "no solution: {}", reason),
+            AocError::Usage(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
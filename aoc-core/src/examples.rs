@@ -0,0 +1,146 @@
+use std::fs;
+use std::path::Path;
+
+use crate::error::{AocError, Result};
+use crate::solution::Solution;
+use crate::toml::{parse_tables, Table};
+
+/// A worked example from the puzzle text and the answers it is given.
+#[derive(Debug)]
+pub struct Example {
+    /// Where the input comes from, for failure messages.
+    pub source: String,
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// The fenced code blocks of a markdown document, in order.
+pub fn code_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks: Vec<String> = Vec::new();
+    let mut current: Option<String> = None;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            match current.take() {
+                Some(block) => blocks.push(block),
+                None => current = Some(String::new()),
+            }
+            continue;
+        }
+        if let Some(block) = current.as_mut() {
+            block.push_str(line);
+            block.push('\n');
+        }
+    }
+
+    return blocks;
+}
+
+fn read(filename: &Path) -> Result<String> {
+    return fs::read_to_string(filename).map_err(|e| AocError::Io {
+        file: filename.display().to_string(),
+        source: e,
+    });
+}
+
+/// Loads the examples listed in `examples.toml` in `day_dir`. Each
+/// `[[example]]` takes its input either from a `file` in the same directory
+/// or from the `readme`-th fenced block of the day's `README.md`, and gives
+/// the expected `part1` and/or `part2` answers:
+///
+/// ```toml
+/// [[example]]
+/// readme = 1
+/// part1 = "7"
+/// part2 = "5"
+/// ```
+pub fn load_examples(day_dir: &str) -> Result<Vec<Example>> {
+    let day_dir = Path::new(day_dir);
+    let sidecar = day_dir.join("examples.toml");
+    let sidecar_name = sidecar.display().to_string();
+    let tables = parse_tables(&read(&sidecar)?, "example").map_err(|e| e.with_file(&sidecar_name))?;
+
+    let mut examples: Vec<Example> = Vec::new();
+    for table in tables {
+        let example = load_example(day_dir, &table).map_err(|e| e.with_file(&sidecar_name))?;
+        examples.push(example);
+    }
+    return Ok(examples);
+}
+
+fn load_example(day_dir: &Path, table: &Table) -> Result<Example> {
+    table.check_keys(&["file", "readme", "part1", "part2"])?;
+
+    let (source, input) = match (table.string("file")?, table.integer("readme")?) {
+        (Some(file), None) => {
+            let input = read(&day_dir.join(&file))?;
+            (file, input)
+        },
+        (None, Some(block)) => {
+            let blocks = code_blocks(&read(&day_dir.join("README.md"))?);
+            let input = usize::try_from(block)
+                .ok()
+                .and_then(|block| block.checked_sub(1))
+                .and_then(|idx| blocks.get(idx))
+                .ok_or(AocError::parse(table.line, &block.to_string(), "no such block in README.md"))?;
+            (format!("README.md block {}", block), input.to_string())
+        },
+        _ => return Err(AocError::parse(table.line, "[[example]]", "expected one of `file` or `readme`")),
+    };
+
+    return Ok(Example {
+        source: source,
+        input: input,
+        part1: table.string("part1")?,
+        part2: table.string("part2")?,
+    });
+}
+
+/// Runs `part` of `solution` on every example of `day_dir` that has an
+/// answer for it, panicking with every mismatch. Used by `example_tests!`.
+pub fn check_examples(solution: &dyn Solution, day_dir: &str, part: u8) {
+    let examples = load_examples(day_dir).unwrap_or_else(|e| panic!("{}", e));
+
+    let mut failures: Vec<String> = Vec::new();
+    for example in &examples {
+        let expected = match part {
+            1 => &example.part1,
+            _ => &example.part2,
+        };
+        let expected = match expected {
+            Some(expected) => expected,
+            None => continue,
+        };
+        let answer = match part {
+            1 => solution.part1(&example.input),
+            _ => solution.part2(&example.input),
+        };
+        match answer {
+            Ok(answer) if &answer == expected => {},
+            Ok(answer) => failures.push(format!("{}: expected {} but got {}", example.source, expected, answer)),
+            Err(error) => failures.push(format!("{}: {}", example.source, error.with_file(&example.source))),
+        }
+    }
+
+    if !failures.is_empty() {
+        panic!("day {} part {}:\n{}", solution.day(), part, failures.join("\n"));
+    }
+}
+
+/// Generates `part1_examples` and `part2_examples` tests that check a
+/// `Solution` against the examples of the crate it is used in.
+#[macro_export]
+macro_rules! example_tests {
+    ($solution:expr) => {
+        #[test]
+        fn part1_examples() {
+            $crate::examples::check_examples(&$solution, env!("CARGO_MANIFEST_DIR"), 1);
+        }
+
+        #[test]
+        fn part2_examples() {
+            $crate::examples::check_examples(&$solution, env!("CARGO_MANIFEST_DIR"), 2);
+        }
+    };
+}
@@ -0,0 +1,59 @@
+/// A fixed-size, row-major 2D grid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from row-major `cells`, `width` values per row.
+    pub fn from_cells(cells: Vec<T>, width: usize) -> Grid<T> {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        return Grid { width: width, height: height, cells: cells };
+    }
+
+    pub fn width(&self) -> usize {
+        return self.width;
+    }
+
+    pub fn height(&self) -> usize {
+        return self.height;
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> &T {
+        return &self.cells[self.idx(x, y)];
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: T) {
+        let idx = self.idx(x, y);
+        self.cells[idx] = value;
+    }
+
+    /// Iterates over the cells in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        return self.cells.iter();
+    }
+
+    /// Position of the first cell equal to `value`, as `(x, y)`.
+    pub fn position(&self, value: &T) -> Option<(usize, usize)>
+            where T: PartialEq {
+        let idx = self.cells.iter().position(|cell| cell == value)?;
+        return Some((idx % self.width, idx / self.width));
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        return &self.cells[y * self.width..(y + 1) * self.width];
+    }
+
+    fn idx(&self, x: usize, y: usize) -> usize {
+        return x + self.width * y;
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// A `width` x `height` grid with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T> {
+        return Grid { width: width, height: height, cells: vec![value; width * height] };
+    }
+}
@@ -0,0 +1,104 @@
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use crate::error::{AocError, Result};
+
+/// Reads the whole of `filename` into memory, or standard input if
+/// `filename` is `-`.
+pub fn read_input(filename: &str) -> Result<String> {
+    if filename == "-" {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input).map_err(|e| io_error("<stdin>", e))?;
+        return Ok(input);
+    }
+    return fs::read_to_string(filename).map_err(|e| io_error(filename, e));
+}
+
+/// How long `Follow` waits at the end of a file before looking for new lines.
+const FOLLOW_POLL: Duration = Duration::from_millis(200);
+
+/// Lines of a file as they are appended to it, like `tail -f`: at the end of
+/// the file it waits for more instead of stopping. A line is only returned
+/// once its newline has been written.
+pub struct Follow {
+    reader: Box<dyn BufRead>,
+    filename: String,
+    wait: bool,
+    pending: String,
+}
+
+/// Follows `filename`, or standard input if `filename` is `-`. Standard input
+/// is read as it arrives and ends when it is closed.
+pub fn follow_input(filename: &str) -> Result<Follow> {
+    if filename == "-" {
+        return Ok(Follow {
+            reader: Box::new(BufReader::new(io::stdin())),
+            filename: "<stdin>".to_string(),
+            wait: false,
+            pending: String::new(),
+        });
+    }
+    let file = File::open(filename).map_err(|e| io_error(filename, e))?;
+    return Ok(Follow {
+        reader: Box::new(BufReader::new(file)),
+        filename: filename.to_string(),
+        wait: true,
+        pending: String::new(),
+    });
+}
+
+impl Iterator for Follow {
+    type Item = Result<String>;
+
+    fn next(&mut self) -> Option<Result<String>> {
+        loop {
+            match self.reader.read_line(&mut self.pending) {
+                Ok(0) if self.wait => thread::sleep(FOLLOW_POLL),
+                Ok(0) if self.pending.is_empty() => return None,
+                Ok(0) => return Some(Ok(std::mem::take(&mut self.pending))),
+                Ok(_) if self.pending.ends_with('\n') => {
+                    let line = std::mem::take(&mut self.pending);
+                    return Some(Ok(line.trim_end_matches(['\r', '\n']).to_string()));
+                },
+                Ok(_) => (),
+                Err(e) => return Some(Err(io_error(&self.filename, e))),
+            }
+        }
+    }
+}
+
+fn io_error(filename: &str, source: io::Error) -> AocError {
+    return AocError::Io { file: filename.to_string(), source: source };
+}
+
+/// The first line of `input`, for puzzles given as a single line.
+pub fn first_line(input: &str) -> Result<&str> {
+    return input.lines().next().ok_or(AocError::parse(1, "", "empty input"));
+}
+
+/// Parses `text`, found at `line`, as a number or any other `T`.
+pub fn parse_value<T: FromStr>(text: &str, line: usize) -> Result<T> {
+    let text = text.trim();
+    return text.parse().map_err(|_| AocError::parse(line, text, "invalid value"));
+}
+
+/// Parses every line of `input` as a `T`, ignoring surrounding whitespace.
+pub fn parse_lines<T: FromStr>(input: &str) -> impl Iterator<Item = Result<T>> + '_ {
+    return input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| parse_value(line, idx + 1));
+}
+
+/// Parses a line of comma-separated values, such as `3,4,3,1,2`, found at
+/// `line`.
+pub fn parse_comma_separated<T: FromStr>(text: &str, line: usize) -> Result<Vec<T>> {
+    return text
+        .trim()
+        .split(",")
+        .map(|x| parse_value(x, line))
+        .collect();
+}
@@ -0,0 +1,297 @@
+use std::fmt;
+
+use crate::error::{AocError, Result};
+
+/// A JSON value, enough to write the runner's reports and read them back.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    /// Keys keep their insertion order.
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object(entries: Vec<(&str, Json)>) -> Json {
+        return Json::Object(entries.into_iter().map(|(k, v)| (k.to_string(), v)).collect());
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(entries) => return entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => return None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(n) => return Some(*n),
+            _ => return None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(s) => return Some(s),
+            _ => return None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(values) => return Some(values),
+            _ => return None,
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Json> {
+        let mut parser = Parser { text: text, pos: 0 };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.pos < text.len() {
+            return Err(parser.error("unexpected trailing characters"));
+        }
+        return Ok(value);
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Json {
+        return Json::String(value.to_string());
+    }
+}
+
+impl From<u64> for Json {
+    fn from(value: u64) -> Json {
+        return Json::Number(value as f64);
+    }
+}
+
+impl From<i64> for Json {
+    fn from(value: i64) -> Json {
+        return Json::Number(value as f64);
+    }
+}
+
+impl From<f64> for Json {
+    fn from(value: f64) -> Json {
+        return Json::Number(value);
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter, string: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in string.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+/// Compact JSON, on a single line.
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(b) => write!(f, "{}", b),
+            Json::Number(n) if n.is_finite() => write!(f, "{}", n),
+            Json::Number(_) => write!(f, "null"),
+            Json::String(s) => write_string(f, s),
+            Json::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            },
+            Json::Object(entries) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            },
+        }
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, reason: &str) -> AocError {
+        let line = self.text[..self.pos].matches('\n').count() + 1;
+        let rest: String = self.text[self.pos..].chars().take(20).collect();
+        return AocError::parse(line, &rest, reason);
+    }
+
+    fn peek(&self) -> Option<char> {
+        return self.text[self.pos..].chars().next();
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if !c.is_whitespace() {
+                break;
+            }
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<()> {
+        self.skip_whitespace();
+        if !self.text[self.pos..].starts_with(token) {
+            return Err(self.error(&format!("expected `{}`", token)));
+        }
+        self.pos += token.len();
+        return Ok(());
+    }
+
+    fn value(&mut self) -> Result<Json> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => return self.object(),
+            Some('[') => return self.array(),
+            Some('"') => return self.string().map(Json::String),
+            Some('t') => return self.expect("true").map(|_| Json::Bool(true)),
+            Some('f') => return self.expect("false").map(|_| Json::Bool(false)),
+            Some('n') => return self.expect("null").map(|_| Json::Null),
+            Some(c) if c == '-' || c.is_ascii_digit() => return self.number(),
+            _ => return Err(self.error("expected a value")),
+        }
+    }
+
+    fn number(&mut self) -> Result<Json> {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if !(c.is_ascii_digit() || "+-.eE".contains(c)) {
+                break;
+            }
+            self.pos += 1;
+        }
+        return self.text[start..self.pos]
+            .parse()
+            .map(Json::Number)
+            .map_err(|_| self.error("invalid number"));
+    }
+
+    fn string(&mut self) -> Result<String> {
+        self.expect("\"")?;
+        let mut string = String::new();
+        loop {
+            let c = self.peek().ok_or(self.error("unterminated string"))?;
+            self.pos += c.len_utf8();
+            match c {
+                '"' => return Ok(string),
+                '\\' => {
+                    let escaped = self.peek().ok_or(self.error("unterminated string"))?;
+                    self.pos += escaped.len_utf8();
+                    match escaped {
+                        'b' => string.push('\u{8}'),
+                        'f' => string.push('\u{c}'),
+                        'n' => string.push('\n'),
+                        'r' => string.push('\r'),
+                        't' => string.push('\t'),
+                        'u' => {
+                            let code = self.text.get(self.pos..self.pos + 4)
+                                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                                .and_then(char::from_u32)
+                                .ok_or(self.error("invalid unicode escape"))?;
+                            self.pos += 4;
+                            string.push(code);
+                        },
+                        c => string.push(c),
+                    }
+                },
+                c => string.push(c),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json> {
+        self.expect("[")?;
+        let mut values: Vec<Json> = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Ok(Json::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(']') => {
+                    self.pos += 1;
+                    return Ok(Json::Array(values));
+                },
+                _ => return Err(self.error("expected `,` or `]`")),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Json> {
+        self.expect("{")?;
+        let mut entries: Vec<(String, Json)> = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(Json::Object(entries));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.expect(":")?;
+            entries.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some('}') => {
+                    self.pos += 1;
+                    return Ok(Json::Object(entries));
+                },
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes() {
+        let json = Json::parse(r#"["\b\f\n\r\t\"\\\/", "é", "\é"]"#).unwrap();
+        assert_eq!(json, Json::Array(vec![
+            Json::String("\u{8}\u{c}\n\r\t\"\\/".to_string()),
+            Json::String("é".to_string()),
+            Json::String("é".to_string()),
+        ]));
+    }
+
+    #[test]
+    fn strings_round_trip() {
+        let string = Json::String("tab\there \"quoted\" \u{1} é".to_string());
+        assert_eq!(Json::parse(&string.to_string()).unwrap(), string);
+    }
+}
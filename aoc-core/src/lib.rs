@@ -0,0 +1,31 @@
+//! Shared helpers for the Advent of Code 2021 solutions: input loading and
+//! parsing, the error type, the grid and point types used by several days,
+//! the `Solution` trait the `aoc` runner dispatches through, and the store of
+//! known answers it verifies against, and the worked examples each day is
+//! tested on. `bench` times the solvers and `json` reads and writes the
+//! reports. `window` holds the sliding windows used on series of readings.
+
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod error;
+pub mod examples;
+pub mod grid;
+pub mod input;
+pub mod json;
+pub mod log;
+pub mod output;
+pub mod point;
+pub mod solution;
+pub mod toml;
+pub mod window;
+
+pub use error::{AocError, Result};
+pub use grid::Grid;
+pub use input::{
+    first_line, follow_input, parse_comma_separated, parse_lines, parse_value,
+    read_input, Follow,
+};
+pub use point::Point;
+pub use solution::Solution;
+pub use window::{CheckedSum, Overflow, Window, WindowSums, WindowSumsExt};
@@ -0,0 +1,123 @@
+//! Leveled diagnostics on standard error, so standard output only carries
+//! the answers. The level defaults to `warn` and is set from the `AOC_LOG`
+//! environment variable (`error`, `warn`, `info`, `debug` or `trace`) or from
+//! `-q`, `-v`, `-vv` and `-vvv` on the command line.
+
+use std::env;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::cli::Args;
+use crate::error::{AocError, Result};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    /// Results worth knowing about, such as intermediate values of a part.
+    Info,
+    Debug,
+    /// Every step of a solver.
+    Trace,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Warn as u8);
+
+impl Level {
+    fn from_u8(value: u8) -> Level {
+        return match value {
+            1 => Level::Error,
+            2 => Level::Warn,
+            3 => Level::Info,
+            4 => Level::Debug,
+            _ => Level::Trace,
+        };
+    }
+
+    pub fn from_name(name: &str) -> Option<Level> {
+        return match name.to_ascii_lowercase().as_str() {
+            "error" => Some(Level::Error),
+            "warn" => Some(Level::Warn),
+            "info" => Some(Level::Info),
+            "debug" => Some(Level::Debug),
+            "trace" => Some(Level::Trace),
+            _ => None,
+        };
+    }
+}
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    return Level::from_u8(LEVEL.load(Ordering::Relaxed));
+}
+
+pub fn enabled(level: Level) -> bool {
+    return level <= self::level();
+}
+
+/// Writes `message` to standard error if `level` is enabled. Use the
+/// `error!` ... `trace!` macros instead.
+pub fn log(level: Level, message: fmt::Arguments) {
+    if enabled(level) {
+        eprintln!("{}", message);
+    }
+}
+
+/// Sets the level from `AOC_LOG` and then from the `-q`/`--quiet` and
+/// `-v`/`--verbose` flags in `args`, each `v` raising it one step above
+/// `warn`.
+pub fn init(args: &mut Args) -> Result<()> {
+    if let Ok(name) = env::var("AOC_LOG") {
+        let level = Level::from_name(&name)
+            .ok_or(AocError::Usage(format!("invalid AOC_LOG level {}", name)))?;
+        set_level(level);
+    }
+
+    let mut verbosity = 0;
+    while args.flag("-v") || args.flag("--verbose") {
+        verbosity += 1;
+    }
+    while args.flag("-vv") {
+        verbosity += 2;
+    }
+    while args.flag("-vvv") {
+        verbosity += 3;
+    }
+    if args.flag("-q") || args.flag("--quiet") {
+        if verbosity > 0 {
+            return Err(AocError::usage("-q and -v are mutually exclusive"));
+        }
+        set_level(Level::Error);
+    } else if verbosity > 0 {
+        set_level(Level::from_u8(Level::Warn as u8 + verbosity));
+    }
+    return Ok(());
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)*) => { $crate::log::log($crate::log::Level::Error, format_args!($($arg)*)) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)*) => { $crate::log::log($crate::log::Level::Warn, format_args!($($arg)*)) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => { $crate::log::log($crate::log::Level::Info, format_args!($($arg)*)) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => { $crate::log::log($crate::log::Level::Debug, format_args!($($arg)*)) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => { $crate::log::log($crate::log::Level::Trace, format_args!($($arg)*)) };
+}
@@ -0,0 +1,76 @@
+use std::time::{Duration, Instant};
+
+use crate::cli::Args;
+use crate::error::{AocError, Result};
+use crate::json::Json;
+use crate::solution::Solution;
+
+/// How answers are printed on standard output.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Format {
+    /// `Day 1 part 1: 1292`
+    Text,
+    /// One `{"day":1,"part":1,"answer":"1292","elapsed_ms":0.41}` per line.
+    Json,
+}
+
+impl Format {
+    /// Takes `--format text|json`, defaulting to text.
+    pub fn from_args(args: &mut Args) -> Result<Format> {
+        match args.value("--format")?.as_deref() {
+            None | Some("text") => return Ok(Format::Text),
+            Some("json") => return Ok(Format::Json),
+            Some(other) => return Err(AocError::Usage(format!("invalid format {}, expected text or json", other))),
+        }
+    }
+}
+
+/// The answer to one part, and how long it took. `channel` names the part of
+/// the input it is for, if the input has several.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub day: u8,
+    pub channel: Option<String>,
+    pub part: u8,
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+impl Record {
+    pub fn to_json(&self) -> Json {
+        let mut entries = vec![("day", Json::from(self.day as u64))];
+        if let Some(channel) = &self.channel {
+            entries.push(("channel", Json::from(channel.as_str())));
+        }
+        entries.push(("part", Json::from(self.part as u64)));
+        entries.push(("answer", Json::from(self.answer.as_str())));
+        entries.push(("elapsed_ms", Json::from(self.elapsed.as_secs_f64() * 1000.0)));
+        return Json::object(entries);
+    }
+
+    pub fn print(&self, format: Format) {
+        match format {
+            Format::Text => match &self.channel {
+                Some(channel) => println!("Day {} {} part {}: {}", self.day, channel, self.part, self.answer),
+                None => println!("Day {} part {}: {}", self.day, self.part, self.answer),
+            },
+            Format::Json => println!("{}", self.to_json()),
+        }
+    }
+}
+
+/// Runs and times `part` of `solution`.
+pub fn run_part(solution: &dyn Solution, part: u8, input: &str) -> Result<Record> {
+    let start = Instant::now();
+    let answer = match part {
+        1 => solution.part1(input)?,
+        _ => solution.part2(input)?,
+    };
+    return Ok(Record {
+        day: solution.day(),
+        channel: None,
+        part: part,
+        answer: answer,
+        elapsed: start.elapsed(),
+    });
+}
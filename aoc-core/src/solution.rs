@@ -0,0 +1,17 @@
+use crate::error::Result;
+
+/// The two puzzles of a day, as seen by the `aoc` runner.
+///
+/// Both parts receive the whole puzzle input and return the answer already
+/// formatted, so days with different answer types can share one registry.
+pub trait Solution {
+    /// Day of the calendar, starting at 1.
+    fn day(&self) -> u8;
+
+    /// Path of the puzzle input used when none is given.
+    fn default_input(&self) -> &'static str;
+
+    fn part1(&self, input: &str) -> Result<String>;
+
+    fn part2(&self, input: &str) -> Result<String>;
+}
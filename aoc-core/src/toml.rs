@@ -0,0 +1,86 @@
+use crate::error::{AocError, Result};
+
+/// One `[[name]]` entry of a TOML file: its `key = value` pairs, with values
+/// either integers or basic strings.
+#[derive(Debug)]
+pub struct Table {
+    /// Line of the `[[name]]` header, starting at 1.
+    pub line: usize,
+    entries: Vec<(String, String, usize)>,
+}
+
+/// Parses the subset of TOML used by the answer and example files: comments
+/// and an array of `[[name]]` tables holding integer or string values.
+pub fn parse_tables(contents: &str, name: &str) -> Result<Vec<Table>> {
+    let header = format!("[[{}]]", name);
+    let mut tables: Vec<Table> = Vec::new();
+
+    for (idx, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line == header {
+            tables.push(Table { line: idx + 1, entries: Vec::new() });
+            continue;
+        }
+
+        let (key, value) = match line.split_once('=') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => return Err(AocError::parse(idx + 1, line, "expected `key = value`")),
+        };
+        match tables.last_mut() {
+            Some(table) => table.entries.push((key.to_string(), value.to_string(), idx + 1)),
+            None => return Err(AocError::parse(idx + 1, line, &format!("expected {} first", header))),
+        }
+    }
+
+    return Ok(tables);
+}
+
+impl Table {
+    fn raw(&self, key: &str) -> Option<(&str, usize)> {
+        return self.entries
+            .iter()
+            .find(|(k, _, _)| k == key)
+            .map(|(_, value, line)| (value.as_str(), *line));
+    }
+
+    /// The string value of `key`, if present.
+    pub fn string(&self, key: &str) -> Result<Option<String>> {
+        let (value, line) = match self.raw(key) {
+            Some(raw) => raw,
+            None => return Ok(None),
+        };
+        return value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .map(|v| Some(v.to_string()))
+            .ok_or(AocError::parse(line, value, "expected a quoted string"));
+    }
+
+    /// The integer value of `key`, if present.
+    pub fn integer(&self, key: &str) -> Result<Option<i64>> {
+        return match self.raw(key) {
+            Some((value, line)) => value
+                .parse()
+                .map(Some)
+                .map_err(|_| AocError::parse(line, value, "expected an integer")),
+            None => Ok(None),
+        };
+    }
+
+    /// Fails on any key not in `known`.
+    pub fn check_keys(&self, known: &[&str]) -> Result<()> {
+        for (key, _, line) in &self.entries {
+            if !known.contains(&key.as_str()) {
+                return Err(AocError::parse(*line, key, "unknown key"));
+            }
+        }
+        return Ok(());
+    }
+
+    pub fn missing(&self, key: &str) -> AocError {
+        return AocError::parse(self.line, key, "missing key");
+    }
+}
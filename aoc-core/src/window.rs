@@ -0,0 +1,180 @@
+//! Sliding windows over a stream of values, kept as a running sum so each
+//! step costs the same whatever the window size.
+
+/// Integers a window can be summed in, with overflow detected.
+pub trait CheckedSum: Copy + Default {
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_checked_sum {
+    ($($t:ty),*) => {
+        $(
+            impl CheckedSum for $t {
+                fn checked_add(self, other: $t) -> Option<$t> {
+                    return <$t>::checked_add(self, other);
+                }
+
+                fn checked_sub(self, other: $t) -> Option<$t> {
+                    return <$t>::checked_sub(self, other);
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_sum!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// A window sum that does not fit its type. `index` is the position, among
+/// the values pushed, of the one whose entry into the window overflowed.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Overflow {
+    pub index: usize,
+}
+
+/// The last `size` values pushed, in a ring buffer, and their sum. Each push
+/// subtracts the value leaving the window and adds the one entering it, so
+/// memory and time per value do not depend on how many were pushed.
+pub struct Window<T> {
+    size: usize,
+    buffer: Vec<T>,
+    oldest: usize,
+    sum: T,
+    pushed: usize,
+}
+
+impl<T: CheckedSum> Window<T> {
+    /// Panics if `size` is 0.
+    pub fn new(size: usize) -> Window<T> {
+        assert!(size > 0, "window size must be at least 1");
+        return Window {
+            size: size,
+            buffer: Vec::with_capacity(size),
+            oldest: 0,
+            sum: T::default(),
+            pushed: 0,
+        };
+    }
+
+    pub fn size(&self) -> usize {
+        return self.size;
+    }
+
+    /// Slides the window over `value`. Returns the new sum once `size` values
+    /// have been pushed, and leaves the window untouched if it overflows.
+    pub fn push(&mut self, value: T) -> Result<Option<T>, Overflow> {
+        let index = self.pushed;
+        self.sum = self.slide(value).ok_or(Overflow { index: index })?;
+        self.pushed = self.pushed + 1;
+
+        if self.buffer.len() < self.size {
+            self.buffer.push(value);
+            if self.buffer.len() < self.size {
+                return Ok(None);
+            }
+        } else {
+            self.buffer[self.oldest] = value;
+            self.oldest = (self.oldest + 1) % self.size;
+        }
+        return Ok(Some(self.sum));
+    }
+
+    /// The sum after pushing `value`, `None` if it overflows.
+    fn slide(&self, value: T) -> Option<T> {
+        if self.buffer.len() < self.size {
+            return self.sum.checked_add(value);
+        }
+        // With signed values only one of the two orders may stay in range.
+        let leaving = self.buffer[self.oldest];
+        return self.sum.checked_sub(leaving).and_then(|sum| sum.checked_add(value))
+            .or_else(|| self.sum.checked_add(value).and_then(|sum| sum.checked_sub(leaving)));
+    }
+}
+
+/// Sums of every `size` consecutive values of `iter`, through a `Window`.
+/// Ends after the first `Overflow`.
+pub struct WindowSums<I: Iterator> {
+    iter: I,
+    window: Window<I::Item>,
+    overflowed: bool,
+}
+
+impl<I> WindowSums<I> where I: Iterator, I::Item: CheckedSum {
+    /// Panics if `size` is 0.
+    pub fn new(iter: I, size: usize) -> WindowSums<I> {
+        return WindowSums { iter: iter, window: Window::new(size), overflowed: false };
+    }
+}
+
+impl<I> Iterator for WindowSums<I> where I: Iterator, I::Item: CheckedSum {
+    type Item = Result<I::Item, Overflow>;
+
+    fn next(&mut self) -> Option<Result<I::Item, Overflow>> {
+        if self.overflowed {
+            return None;
+        }
+        loop {
+            match self.window.push(self.iter.next()?) {
+                Ok(Some(sum)) => return Some(Ok(sum)),
+                Ok(None) => continue,
+                Err(overflow) => {
+                    self.overflowed = true;
+                    return Some(Err(overflow));
+                },
+            }
+        }
+    }
+}
+
+/// Adds `window_sums` to every iterator over integers.
+pub trait WindowSumsExt: Iterator + Sized {
+    /// Sums of every `size` consecutive values, the first one once `size`
+    /// values have been seen. Panics if `size` is 0.
+    fn window_sums(self, size: usize) -> WindowSums<Self> where Self::Item: CheckedSum {
+        return WindowSums::new(self, size);
+    }
+}
+
+impl<I: Iterator> WindowSumsExt for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflow_names_the_value_and_leaves_the_window() {
+        let mut window: Window<i64> = Window::new(2);
+        assert_eq!(window.push(i64::MAX), Ok(None));
+        assert_eq!(window.push(1), Err(Overflow { index: 1 }));
+        // The 1 was not pushed, so the 0 takes its place.
+        assert_eq!(window.push(0), Ok(Some(i64::MAX)));
+        assert_eq!(window.push(i64::MIN), Ok(Some(i64::MIN)));
+        assert_eq!(window.push(-1), Err(Overflow { index: 3 }));
+    }
+
+    #[test]
+    fn signed_sums_try_the_other_order() {
+        // Dropping the -1 first would go past i64::MAX, adding the new -1
+        // first does not.
+        let mut window: Window<i64> = Window::new(3);
+        assert_eq!(window.push(-1), Ok(None));
+        assert_eq!(window.push(i64::MAX), Ok(None));
+        assert_eq!(window.push(1), Ok(Some(i64::MAX)));
+        assert_eq!(window.push(-1), Ok(Some(i64::MAX)));
+
+        let mut window: Window<i64> = Window::new(3);
+        assert_eq!(window.push(1), Ok(None));
+        assert_eq!(window.push(i64::MIN), Ok(None));
+        assert_eq!(window.push(-1), Ok(Some(i64::MIN)));
+        assert_eq!(window.push(1), Ok(Some(i64::MIN)));
+    }
+
+    #[test]
+    fn window_sums_end_after_an_overflow() {
+        let sums: Vec<Result<i64, Overflow>> = vec![0, 1, i64::MAX, 5, 6].into_iter().window_sums(2).collect();
+        assert_eq!(sums, vec![Ok(1), Err(Overflow { index: 2 })]);
+
+        let sums: Vec<Result<u8, Overflow>> = vec![200u8, 100, 1].into_iter().window_sums(2).collect();
+        assert_eq!(sums, vec![Err(Overflow { index: 1 })]);
+    }
+}
@@ -0,0 +1,33 @@
+//! Benchmarks every part of every day on its own input, e.g.
+//! `cargo bench -p aoc` or `cargo bench -p aoc -- day7` to select by name.
+
+use aoc::solutions;
+use aoc_core::bench::{measure, CountingAllocator};
+use aoc_core::read_input;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const ITERATIONS: usize = 20;
+
+fn main() {
+    // cargo passes `--bench`; anything else is a filter on `dayN/partP`
+    let filters: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with("--"))
+        .collect();
+
+    for solution in solutions() {
+        let input = read_input(solution.default_input()).unwrap();
+        for part in [1, 2] {
+            let name = format!("day{}/part{}", solution.day(), part);
+            if !filters.is_empty() && !filters.iter().any(|f| name.contains(f.as_str())) {
+                continue;
+            }
+            match measure(solution.as_ref(), part, &input, ITERATIONS) {
+                Ok(measurement) => println!("{}", measurement),
+                Err(error) => println!("{}: {}", name, error),
+            }
+        }
+    }
+}
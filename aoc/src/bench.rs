@@ -0,0 +1,71 @@
+use std::fs;
+
+use aoc::solutions;
+use aoc_core::bench::{measure, median_change, results_from_json, results_to_json, Measurement};
+use aoc_core::cli::Args;
+use aoc_core::json::Json;
+use aoc_core::{read_input, AocError, Result, Solution};
+
+use crate::{day_arg, find_solution, part_arg};
+
+const DEFAULT_ITERATIONS: usize = 10;
+
+fn load_baseline(filename: &str) -> Result<Vec<Measurement>> {
+    let contents = fs::read_to_string(filename)
+        .map_err(|e| AocError::Io { file: filename.to_string(), source: e })?;
+    return Json::parse(&contents)
+        .and_then(|json| results_from_json(&json))
+        .map_err(|e| e.with_file(filename));
+}
+
+/// Times the selected days and parts, printing a line per part and
+/// optionally saving the results as JSON and comparing them with a previous
+/// run.
+pub fn bench(mut args: Args) -> Result<()> {
+    let day = day_arg(&mut args)?;
+    let part = part_arg(&mut args)?;
+    let filename = args.value("--input")?;
+    let iterations = match args.value("--iterations")? {
+        Some(n) => n.parse().map_err(|_| AocError::Usage(format!("invalid number of iterations {}", n)))?,
+        None => DEFAULT_ITERATIONS,
+    };
+    let output = args.value("--output")?;
+    let baseline = match args.value("--baseline")? {
+        Some(baseline) => load_baseline(&baseline)?,
+        None => Vec::new(),
+    };
+    args.finish()?;
+
+    let solutions = solutions();
+    let selected: Vec<&dyn Solution> = match day {
+        Some(day) => vec![find_solution(&solutions, day)?],
+        None => solutions.iter().map(|s| s.as_ref()).collect(),
+    };
+    if filename.is_some() && selected.len() > 1 {
+        return Err(AocError::usage("--input needs a --day"));
+    }
+
+    let mut measurements: Vec<Measurement> = Vec::new();
+    for solution in selected {
+        let filename = filename.as_deref().unwrap_or(solution.default_input());
+        let input = read_input(filename)?;
+        for number in part.numbers() {
+            let measurement = measure(solution, number, &input, iterations)
+                .map_err(|e| e.with_file(filename))?;
+            let previous = baseline
+                .iter()
+                .find(|b| b.day == measurement.day && b.part == measurement.part);
+            match previous {
+                Some(previous) => println!("{}  {:+.1}% vs baseline", measurement, median_change(&measurement, previous)),
+                None => println!("{}", measurement),
+            }
+            measurements.push(measurement);
+        }
+    }
+
+    if let Some(output) = output {
+        fs::write(&output, format!("{}\n", results_to_json(&measurements)))
+            .map_err(|e| AocError::Io { file: output.to_string(), source: e })?;
+    }
+    return Ok(());
+}
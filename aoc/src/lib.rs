@@ -0,0 +1,17 @@
+//! The registry of implemented days, shared by the `aoc` runner and the
+//! benchmarks.
+
+use aoc_core::Solution;
+
+pub fn solutions() -> Vec<Box<dyn Solution>> {
+    return vec![
+        Box::new(day1::Day1),
+        Box::new(day2::Day2),
+        Box::new(day3::Day3),
+        Box::new(day4::Day4),
+        Box::new(day5::Day5),
+        Box::new(day6::Day6),
+        Box::new(day7::Day7),
+        Box::new(day8::Day8),
+    ];
+}
@@ -0,0 +1,126 @@
+mod bench;
+mod verify;
+
+use std::process;
+
+use aoc::solutions;
+use aoc_core::bench::CountingAllocator;
+use aoc_core::cli::{self, Args};
+use aoc_core::log;
+use aoc_core::output::{run_part, Format};
+use aoc_core::{read_input, AocError, Result, Solution};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const USAGE: &str = "\
+Usage:
+    aoc list
+    aoc run --day N [--part 1|2|all] [--input PATH|-] [--format text|json]
+    aoc verify [--day N] [--input PATH|-] [--update]
+    aoc bench [--day N] [--part 1|2|all] [--input PATH|-] [--iterations N]
+              [--output FILE] [--baseline FILE]
+
+Every command takes -v, -vv or -vvv for more diagnostics on stderr and -q for
+fewer; AOC_LOG=error|warn|info|debug|trace sets the default.";
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Part {
+    One,
+    Two,
+    All,
+}
+
+impl Part {
+    fn from_string(string: &str) -> Result<Part> {
+        return match string {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            "all" => Ok(Part::All),
+            _ => Err(AocError::Usage(format!("invalid part {}, expected 1, 2 or all", string))),
+        };
+    }
+
+    fn numbers(&self) -> Vec<u8> {
+        return match self {
+            Part::One => vec![1],
+            Part::Two => vec![2],
+            Part::All => vec![1, 2],
+        };
+    }
+}
+
+fn list() {
+    for solution in solutions() {
+        println!("day {}: parts 1, 2", solution.day());
+    }
+}
+
+/// Runs one part of `solution`, naming `filename` in parse errors.
+fn solve(solution: &dyn Solution, part: u8, input: &str, filename: &str) -> Result<String> {
+    return run_part(solution, part, input)
+        .map(|record| record.answer)
+        .map_err(|error| error.with_file(filename));
+}
+
+fn day_arg(args: &mut Args) -> Result<Option<u8>> {
+    return match args.value("--day")? {
+        Some(day) => day.parse().map(Some).map_err(|_| AocError::Usage(format!("invalid day {}", day))),
+        None => Ok(None),
+    };
+}
+
+fn find_solution(solutions: &[Box<dyn Solution>], day: u8) -> Result<&dyn Solution> {
+    return solutions
+        .iter()
+        .find(|s| s.day() == day)
+        .map(|s| s.as_ref())
+        .ok_or(AocError::Usage(format!("day {} is not implemented", day)));
+}
+
+fn part_arg(args: &mut Args) -> Result<Part> {
+    return match args.value("--part")? {
+        Some(part) => Part::from_string(&part),
+        None => Ok(Part::All),
+    };
+}
+
+fn run(mut args: Args) -> Result<()> {
+    let day = day_arg(&mut args)?.ok_or(AocError::usage("missing --day"))?;
+    let part = part_arg(&mut args)?;
+    let filename = args.value("--input")?;
+    let format = Format::from_args(&mut args)?;
+    args.finish()?;
+
+    let solutions = solutions();
+    let solution = find_solution(&solutions, day)?;
+
+    let filename = filename.as_deref().unwrap_or(solution.default_input());
+    let input = read_input(filename)?;
+    for number in part.numbers() {
+        let record = run_part(solution, number, &input).map_err(|error| error.with_file(filename))?;
+        record.print(format);
+    }
+    return Ok(());
+}
+
+fn main() {
+    let mut args = Args::from_env();
+    if let Err(error) = log::init(&mut args) {
+        cli::exit_with_error(&error);
+    }
+    let result = match args.subcommand().as_deref() {
+        Some("list") => args.finish().map(|_| list()),
+        Some("run") => run(args),
+        Some("bench") => bench::bench(args),
+        Some("verify") => match verify::verify(args) {
+            Ok(false) => process::exit(1),
+            other => other.map(|_| ()),
+        },
+        _ => Err(AocError::usage(USAGE)),
+    };
+
+    if let Err(error) = result {
+        cli::exit_with_error(&error);
+    }
+}
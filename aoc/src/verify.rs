@@ -0,0 +1,82 @@
+use std::path::Path;
+
+use aoc::solutions;
+use aoc_core::answers::{input_hash, Answers};
+use aoc_core::cli::Args;
+use aoc_core::{read_input, Result, Solution};
+
+use crate::{day_arg, find_solution, solve};
+
+/// Where the known answers of `solution` live: `answers.toml` next to its
+/// default input.
+fn answers_file(solution: &dyn Solution) -> String {
+    let input = Path::new(solution.default_input());
+    return input.with_file_name("answers.toml").to_string_lossy().into_owned();
+}
+
+/// Re-runs both parts of `solution` and compares them with the recorded
+/// answers, recording new or changed ones if `update` is set. Returns whether
+/// every part matched or was recorded.
+fn verify_day(solution: &dyn Solution, filename: Option<&str>, update: bool) -> Result<bool> {
+    let day = solution.day();
+    let filename = filename.unwrap_or(solution.default_input());
+    let input = read_input(filename)?;
+    let hash = input_hash(&input);
+
+    let answers_file = answers_file(solution);
+    let mut answers = Answers::load(&answers_file)?;
+    let mut ok = true;
+
+    for part in [1, 2] {
+        let answer = match solve(solution, part, &input, filename) {
+            Ok(answer) => answer,
+            Err(error) => {
+                println!("day {} part {}: FAILED: {}", day, part, error);
+                ok = false;
+                continue;
+            },
+        };
+        match answers.get(part, &hash) {
+            Some(known) if known == answer => {
+                println!("day {} part {}: ok ({})", day, part, answer);
+                continue;
+            },
+            Some(known) => {
+                println!("day {} part {}: CHANGED, expected {} but got {}", day, part, known, answer);
+            },
+            None => {
+                println!("day {} part {}: NEW answer {}", day, part, answer);
+            },
+        }
+        if update {
+            answers.set(part, &hash, &answer);
+        } else {
+            ok = false;
+        }
+    }
+
+    if update {
+        answers.save(&answers_file)?;
+    }
+    return Ok(ok);
+}
+
+/// Verifies the selected days, returning whether they all passed.
+pub fn verify(mut args: Args) -> Result<bool> {
+    let day = day_arg(&mut args)?;
+    let filename = args.value("--input")?;
+    let update = args.flag("--update");
+    args.finish()?;
+
+    let solutions = solutions();
+    let selected: Vec<&dyn Solution> = match day {
+        Some(day) => vec![find_solution(&solutions, day)?],
+        None => solutions.iter().map(|s| s.as_ref()).collect(),
+    };
+
+    let mut ok = true;
+    for solution in selected {
+        ok &= verify_day(solution, filename.as_deref(), update)?;
+    }
+    return Ok(ok);
+}
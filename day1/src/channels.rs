@@ -0,0 +1,258 @@
+//! Recordings from several transducers: CSV or TSV with a header naming the
+//! depth channels, optionally after a timestamp column. A plain report, one
+//! depth per line, is a recording with a single `depth` channel.
+//!
+//! A malformed first depth such as `a1` looks like a header with one column,
+//! so a header is only taken for granted when it names several columns and
+//! the line after it has as many fields. Otherwise the caller has to say the
+//! input is a recording.
+
+use std::collections::HashSet;
+
+use aoc_core::{parse_value, AocError, Result};
+
+use crate::{parse, Depth};
+
+/// The depths of one channel, read from consecutive lines starting at
+/// `first_line`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Channel {
+    pub name: String,
+    pub first_line: usize,
+    pub depths: Vec<Depth>,
+}
+
+impl Channel {
+    /// Runs `analysis` on the depths, which numbers them from line 1, and
+    /// moves any parse error to the line the depth was really read from.
+    pub fn analyse<R>(&self, analysis: impl FnOnce(&[Depth]) -> Result<R>) -> Result<R> {
+        return analysis(&self.depths).map_err(|e| e.offset_lines(self.first_line - 1));
+    }
+}
+
+/// How `Recording::combine` merges the channels of a sample into one depth.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Combine {
+    Min,
+    Max,
+    /// The mean, rounded to the nearest depth, halves up.
+    Average,
+}
+
+impl Combine {
+    pub fn from_name(name: &str) -> Option<Combine> {
+        return match name {
+            "min" => Some(Combine::Min),
+            "max" => Some(Combine::Max),
+            "avg" | "average" => Some(Combine::Average),
+            _ => None,
+        };
+    }
+
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Combine::Min => "min",
+            Combine::Max => "max",
+            Combine::Average => "avg",
+        };
+    }
+
+    fn apply(&self, depths: &[Depth]) -> Depth {
+        match self {
+            Combine::Min => return *depths.iter().min().unwrap(),
+            Combine::Max => return *depths.iter().max().unwrap(),
+            Combine::Average => {
+                let count = depths.len() as i128;
+                let sum: i128 = depths.iter().map(|&d| d as i128).sum();
+                // Within the range of the depths, so it fits a `Depth`.
+                return (2 * sum + count).div_euclid(2 * count) as Depth;
+            },
+        }
+    }
+}
+
+/// Every channel of a recording. `timestamps` has one entry per sample when
+/// the first column is `timestamp` or `time`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recording {
+    /// Whether the input had a header, as opposed to a plain report.
+    pub labelled: bool,
+    pub timestamps: Option<Vec<String>>,
+    pub channels: Vec<Channel>,
+}
+
+impl Recording {
+    pub fn channel(&self, name: &str) -> Option<&Channel> {
+        return self.channels.iter().find(|c| c.name == name);
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        return self.channels.iter().map(|c| c.name.as_str()).collect();
+    }
+
+    /// A channel holding, for each sample, the depths of all the channels
+    /// merged with `how`.
+    pub fn combine(&self, how: Combine) -> Channel {
+        let first = &self.channels[0];
+        let mut depths: Vec<Depth> = Vec::with_capacity(first.depths.len());
+        let mut sample: Vec<Depth> = Vec::with_capacity(self.channels.len());
+        for idx in 0..first.depths.len() {
+            sample.clear();
+            sample.extend(self.channels.iter().map(|c| c.depths[idx]));
+            depths.push(how.apply(&sample));
+        }
+        return Channel { name: how.name().to_string(), first_line: first.first_line, depths: depths };
+    }
+}
+
+/// Whether `name` can name a channel: it starts with a letter or `_`, so a
+/// malformed depth is not taken for a header.
+fn is_name(name: &str) -> bool {
+    return name.starts_with(|c: char| c.is_alphabetic() || c == '_');
+}
+
+/// Splits a CSV or TSV line into trimmed fields.
+fn fields(line: &str, delimiter: char) -> Vec<&str> {
+    return line.split(delimiter).map(|field| field.trim()).collect();
+}
+
+/// Whether the first line of `input` is a header: every field names a
+/// channel and, unless `expect_header`, there are several fields and as many
+/// on the next line.
+fn is_header(input: &str, delimiter: char, expect_header: bool) -> bool {
+    let mut lines = input.lines();
+    let names = fields(lines.next().unwrap_or(""), delimiter);
+    if !names.iter().all(|n| is_name(n)) {
+        return false;
+    }
+    if expect_header {
+        return true;
+    }
+    return names.len() > 1 && lines.next().is_some_and(|line| fields(line, delimiter).len() == names.len());
+}
+
+/// Parses a recording. If the first line is a header, tab separated if it
+/// has a tab and comma separated if not, every other line has one field per
+/// column; otherwise the input is a plain report. With `expect_header`, as
+/// for a `.csv` file, a header with a single column is accepted too.
+pub fn parse_recording(input: &str, expect_header: bool) -> Result<Recording> {
+    let header = input.lines().next().unwrap_or("");
+    let delimiter = if header.contains('\t') { '\t' } else { ',' };
+    let mut names = fields(header, delimiter);
+    if !is_header(input, delimiter, expect_header) {
+        let channel = Channel { name: "depth".to_string(), first_line: 1, depths: parse(input)? };
+        return Ok(Recording { labelled: false, timestamps: None, channels: vec![channel] });
+    }
+
+    let has_timestamp = names.first().is_some_and(|n| n.eq_ignore_ascii_case("timestamp") || n.eq_ignore_ascii_case("time"));
+    if has_timestamp {
+        names.remove(0);
+    }
+    if names.is_empty() {
+        return Err(AocError::parse(1, header, "expected a header naming the depth channels"));
+    }
+    let mut seen: HashSet<&str> = HashSet::new();
+    if let Some(name) = names.iter().find(|&&n| !seen.insert(n)) {
+        return Err(AocError::parse(1, name, "channel named twice"));
+    }
+
+    let mut timestamps: Vec<String> = Vec::new();
+    let mut channels: Vec<Channel> = names
+        .iter()
+        .map(|name| Channel { name: name.to_string(), first_line: 2, depths: Vec::new() })
+        .collect();
+    let columns = names.len() + has_timestamp as usize;
+    for (idx, line) in input.lines().enumerate().skip(1) {
+        let mut values = fields(line, delimiter);
+        if values.len() != columns {
+            return Err(AocError::parse(idx + 1, line, &format!("expected {} fields", columns)));
+        }
+        if has_timestamp {
+            timestamps.push(values.remove(0).to_string());
+        }
+        for (channel, value) in channels.iter_mut().zip(values) {
+            channel.depths.push(parse_value(value, idx + 1)?);
+        }
+    }
+
+    return Ok(Recording {
+        labelled: true,
+        timestamps: if has_timestamp { Some(timestamps) } else { None },
+        channels: channels,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn depths(recording: &Recording) -> Vec<(&str, usize, Vec<Depth>)> {
+        return recording.channels.iter().map(|c| (c.name.as_str(), c.first_line, c.depths.clone())).collect();
+    }
+
+    #[test]
+    fn plain_reports_have_one_channel() {
+        let recording = parse_recording("199\n200\n208\n", false).unwrap();
+        assert!(!recording.labelled);
+        assert_eq!(recording.timestamps, None);
+        assert_eq!(depths(&recording), vec![("depth", 1, vec![199, 200, 208])]);
+        assert_eq!(depths(&parse_recording("199\n200\n", true).unwrap()), vec![("depth", 1, vec![199, 200])]);
+    }
+
+    #[test]
+    fn bad_first_depths_are_not_headers() {
+        for input in ["a1\n199\n200\n", "a,b\n199\n200\n", "a,b\n"] {
+            let error = parse_recording(input, false).unwrap_err();
+            assert!(error.to_string().starts_with("1: "), "{}: {}", input, error);
+        }
+        let recording = parse_recording("a1\n199\n200\n", true).unwrap();
+        assert_eq!(depths(&recording), vec![("a1", 2, vec![199, 200])]);
+    }
+
+    #[test]
+    fn headers_name_the_channels() {
+        let recording = parse_recording("port,starboard\n1,2\n3,4\n", false).unwrap();
+        assert!(recording.labelled);
+        assert_eq!(recording.timestamps, None);
+        assert_eq!(depths(&recording), vec![("port", 2, vec![1, 3]), ("starboard", 2, vec![2, 4])]);
+        assert_eq!(recording.names(), vec!["port", "starboard"]);
+
+        let recording = parse_recording("Time\ta\tb\n00:01\t-1\t2\n00:02\t3\t4\n", false).unwrap();
+        assert_eq!(recording.timestamps, Some(vec!["00:01".to_string(), "00:02".to_string()]));
+        assert_eq!(depths(&recording), vec![("a", 2, vec![-1, 3]), ("b", 2, vec![2, 4])]);
+
+        // With the timestamp, a single channel has two columns.
+        let recording = parse_recording("timestamp,depth\n1,199\n2,200\n", false).unwrap();
+        assert_eq!(depths(&recording), vec![("depth", 2, vec![199, 200])]);
+    }
+
+    #[test]
+    fn malformed_recordings() {
+        let error = |input: &str| parse_recording(input, true).unwrap_err().to_string();
+        assert_eq!(error("a,a\n1,2\n"), "1: channel named twice: \"a\"");
+        assert_eq!(error("time\n1\n"), "1: expected a header naming the depth channels: \"time\"");
+        assert_eq!(error("a,b\n1,2\n3\n"), "3: expected 2 fields: \"3\"");
+        assert!(error("a,b\n1,2\n3,x\n").starts_with("3: "));
+    }
+
+    #[test]
+    fn analysis_errors_point_at_the_line() {
+        let recording = parse_recording("a,b\n1,2\n3,4\n", false).unwrap();
+        let channel = recording.channel("b").unwrap();
+        let error = channel.analyse(|_| -> Result<()> { Err(AocError::parse(2, "4", "too deep")) }).unwrap_err();
+        assert_eq!(error.to_string(), "3: too deep: \"4\"");
+    }
+
+    #[test]
+    fn combine_samples() {
+        let recording = parse_recording("a,b,c\n1,2,4\n-1,-2,0\n5,5,5\n-3,-2,-2\n", false).unwrap();
+        assert_eq!(recording.combine(Combine::Min).depths, vec![1, -2, 5, -3]);
+        assert_eq!(recording.combine(Combine::Max).depths, vec![4, 0, 5, -2]);
+        // 7/3 rounds down, -7/3 up and the halves up.
+        assert_eq!(recording.combine(Combine::Average).depths, vec![2, -1, 5, -2]);
+        let halves = parse_recording("a,b\n1,2\n-1,-2\n", false).unwrap();
+        assert_eq!(halves.combine(Combine::Average).depths, vec![2, -1]);
+        assert_eq!(halves.combine(Combine::Average).name, "avg");
+        assert_eq!(halves.combine(Combine::Average).first_line, 2);
+    }
+}
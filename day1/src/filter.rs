@@ -0,0 +1,149 @@
+//! Smoothing filters run over the depths before counting increases. Part 2 is
+//! the moving sum of 3; noisy recordings want a median first.
+
+use std::collections::VecDeque;
+use std::fmt::Display;
+
+use aoc_core::{CheckedSum, Overflow, Window};
+
+use crate::Depth;
+
+/// Turns a stream of values into a smoothed one, a value at a time.
+pub trait Filter {
+    type Input: Copy + Display;
+    type Value: Copy + PartialOrd + Display;
+
+    /// Pushes `value`. Returns `None` until the filter has seen enough values
+    /// to produce one, and fails if the result does not fit its type.
+    fn push(&mut self, value: Self::Input) -> Result<Option<Self::Value>, Overflow>;
+
+    /// What the filter computes, such as `sum of a window of 3`.
+    fn describe(&self) -> String;
+}
+
+/// Sum of the last `size` values.
+pub struct MovingSum<T> {
+    window: Window<T>,
+}
+
+impl<T: CheckedSum> MovingSum<T> {
+    /// Panics if `size` is 0.
+    pub fn new(size: usize) -> MovingSum<T> {
+        return MovingSum { window: Window::new(size) };
+    }
+}
+
+impl<T: CheckedSum + Ord + Display> Filter for MovingSum<T> {
+    type Input = T;
+    type Value = T;
+
+    fn push(&mut self, value: T) -> Result<Option<T>, Overflow> {
+        return self.window.push(value);
+    }
+
+    fn describe(&self) -> String {
+        return format!("sum of a window of {}", self.window.size());
+    }
+}
+
+/// Mean of the last `size` depths.
+pub struct MovingAverage {
+    window: Window<Depth>,
+}
+
+impl MovingAverage {
+    /// Panics if `size` is 0.
+    pub fn new(size: usize) -> MovingAverage {
+        return MovingAverage { window: Window::new(size) };
+    }
+}
+
+impl Filter for MovingAverage {
+    type Input = Depth;
+    type Value = f64;
+
+    fn push(&mut self, depth: Depth) -> Result<Option<f64>, Overflow> {
+        let size = self.window.size() as f64;
+        return self.window.push(depth).map(|sum| sum.map(|sum| sum as f64 / size));
+    }
+
+    fn describe(&self) -> String {
+        return format!("average of a window of {}", self.window.size());
+    }
+}
+
+/// Median of the last `size` depths; with an even size, the mean of the two
+/// in the middle. Robust to the odd wild reading.
+pub struct MovingMedian {
+    size: usize,
+    values: VecDeque<Depth>,
+}
+
+impl MovingMedian {
+    /// Panics if `size` is 0.
+    pub fn new(size: usize) -> MovingMedian {
+        assert!(size > 0, "window size must be at least 1");
+        return MovingMedian { size: size, values: VecDeque::with_capacity(size) };
+    }
+}
+
+impl Filter for MovingMedian {
+    type Input = Depth;
+    type Value = f64;
+
+    fn push(&mut self, depth: Depth) -> Result<Option<f64>, Overflow> {
+        if self.values.len() == self.size {
+            self.values.pop_front();
+        }
+        self.values.push_back(depth);
+        if self.values.len() < self.size {
+            return Ok(None);
+        }
+
+        let mut sorted: Vec<Depth> = self.values.iter().copied().collect();
+        sorted.sort();
+        let mid = self.size / 2;
+        if self.size % 2 == 1 {
+            return Ok(Some(sorted[mid] as f64));
+        }
+        return Ok(Some((sorted[mid - 1] as i128 + sorted[mid] as i128) as f64 / 2.0));
+    }
+
+    fn describe(&self) -> String {
+        return format!("median of a window of {}", self.size);
+    }
+}
+
+/// Exponential smoothing: each value is `alpha` times the new depth plus
+/// `1 - alpha` times the previous value, starting from the first depth.
+pub struct ExponentialSmoothing {
+    alpha: f64,
+    value: Option<f64>,
+}
+
+impl ExponentialSmoothing {
+    /// Panics unless `alpha` is in `(0, 1]`.
+    pub fn new(alpha: f64) -> ExponentialSmoothing {
+        assert!(alpha > 0.0 && alpha <= 1.0, "alpha must be in (0, 1]");
+        return ExponentialSmoothing { alpha: alpha, value: None };
+    }
+}
+
+impl Filter for ExponentialSmoothing {
+    type Input = Depth;
+    type Value = f64;
+
+    fn push(&mut self, depth: Depth) -> Result<Option<f64>, Overflow> {
+        let depth = depth as f64;
+        let value = match self.value {
+            Some(value) => self.alpha * depth + (1.0 - self.alpha) * value,
+            None => depth,
+        };
+        self.value = Some(value);
+        return Ok(Some(value));
+    }
+
+    fn describe(&self) -> String {
+        return format!("exponential smoothing with alpha {}", self.alpha);
+    }
+}
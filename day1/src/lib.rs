@@ -0,0 +1,184 @@
+//! Day 1: Sonar Sweep. Counts how often a series of depth measurements
+//! increases.
+
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use aoc_core::{info, parse_lines, trace, AocError, CheckedSum, Result, Solution};
+
+pub mod channels;
+pub mod filter;
+pub mod plot;
+pub mod report;
+
+pub use channels::{parse_recording, Channel, Combine, Recording};
+pub use filter::{ExponentialSmoothing, Filter, MovingAverage, MovingMedian, MovingSum};
+pub use report::{report, Report, Trends};
+
+/// The type depths are read and summed in. Recorded dives go much deeper than
+/// the puzzle input, so it is wide and signed.
+pub type Depth = i64;
+
+/// Parses the sonar report, one depth per line.
+pub fn parse<T: FromStr>(input: &str) -> Result<Vec<T>> {
+    return parse_lines(input).collect();
+}
+
+/// How a filtered value compares to the one before it.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Change {
+    Increased,
+    Decreased,
+    Equal,
+}
+
+impl Change {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Change::Increased => "increased",
+            Change::Decreased => "decreased",
+            Change::Equal => "equal",
+        };
+    }
+}
+
+/// A filtered value, produced when the depth at `line` was pushed, and the
+/// counts so far. `change` is `None` for the first value.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Event<T> {
+    pub line: usize,
+    pub value: T,
+    pub change: Option<Change>,
+    pub increases: usize,
+    pub decreases: usize,
+}
+
+/// Compares each filtered value to the previous one as depths are pushed, one
+/// at a time. Only the filter's state is kept, so it can follow a live report.
+pub struct Tracker<F: Filter> {
+    filter: F,
+    last_value: Option<F::Value>,
+    increases: usize,
+    decreases: usize,
+}
+
+impl<F: Filter> Tracker<F> {
+    pub fn new(filter: F) -> Tracker<F> {
+        return Tracker { filter: filter, last_value: None, increases: 0, decreases: 0 };
+    }
+
+    /// Pushes `depth`, read at `line`. Returns `None` until the filter
+    /// produces a value, and fails if that value does not fit its type.
+    pub fn push(&mut self, depth: F::Input, line: usize) -> Result<Option<Event<F::Value>>> {
+        let value = match self.filter.push(depth) {
+            Ok(Some(value)) => value,
+            Ok(None) => return Ok(None),
+            Err(_) => {
+                let reason = format!("{} overflows", self.filter.describe());
+                return Err(AocError::parse(line, &depth.to_string(), &reason));
+            },
+        };
+
+        let change = self.last_value.map(|last_value| match value.partial_cmp(&last_value) {
+            Some(Ordering::Less) => Change::Decreased,
+            Some(Ordering::Greater) => Change::Increased,
+            _ => Change::Equal,
+        });
+        match change {
+            Some(Change::Increased) => self.increases = self.increases + 1,
+            Some(Change::Decreased) => self.decreases = self.decreases + 1,
+            _ => (),
+        }
+        self.last_value = Some(value);
+
+        return Ok(Some(Event {
+            line: line,
+            value: value,
+            change: change,
+            increases: self.increases,
+            decreases: self.decreases,
+        }));
+    }
+
+    pub fn increases(&self) -> usize {
+        return self.increases;
+    }
+
+    pub fn filter(&self) -> &F {
+        return &self.filter;
+    }
+}
+
+/// Number of times a filtered value is larger than the previous one.
+///
+/// Fails if a value does not fit its type, naming the line of the depth that
+/// overflowed, as `depths[i]` is read from line `i + 1`.
+pub fn count_filtered<F: Filter>(depths: &[F::Input], filter: F) -> Result<usize> {
+    let mut tracker = Tracker::new(filter);
+
+    for (idx, &depth) in depths.iter().enumerate() {
+        if let Some(event) = tracker.push(depth, idx + 1)? {
+            match event.change {
+                Some(change) => trace!("{} ({})", event.value, change.name()),
+                None => trace!("{} (N/A - no previous measurement)", event.value),
+            }
+        }
+    }
+    info!("The {} increased a total of {} times", tracker.filter().describe(), tracker.increases());
+    return Ok(tracker.increases());
+}
+
+/// Number of times the sum of `window` consecutive depths is larger than the
+/// sum of the window one measurement earlier. Panics if `window` is 0, and
+/// fails like `count_filtered` on overflow.
+pub fn count_increases<T>(depths: &[T], window: usize) -> Result<usize>
+        where T: CheckedSum + Ord + Display {
+    return count_filtered(depths, MovingSum::new(window));
+}
+
+/// Number of depths larger than the previous one.
+pub fn part1(depths: &[Depth]) -> Result<usize> {
+    return count_increases(depths, 1);
+}
+
+/// Number of times the sum of a three-measurement window is larger than the
+/// previous one.
+pub fn part2(depths: &[Depth]) -> Result<usize> {
+    return count_increases(depths, 3);
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    fn day(&self) -> u8 {
+        return 1;
+    }
+
+    fn default_input(&self) -> &'static str {
+        return concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+    }
+
+    fn part1(&self, input: &str) -> Result<String> {
+        return parse(input).and_then(|depths| part1(&depths)).map(|answer| answer.to_string());
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        return parse(input).and_then(|depths| part2(&depths)).map(|answer| answer.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflow_names_the_line() {
+        let depths: Vec<Depth> = vec![3, 1, Depth::MAX, -2, Depth::MIN];
+        let error = count_increases(&depths, 2).unwrap_err();
+        assert_eq!(error.to_string(), format!("3: sum of a window of 2 overflows: \"{}\"", Depth::MAX));
+        let error = count_increases(&depths[3..], 2).unwrap_err();
+        assert_eq!(error.to_string(), format!("2: sum of a window of 2 overflows: \"{}\"", Depth::MIN));
+        assert_eq!(count_increases(&depths, 1).unwrap(), 1);
+    }
+}
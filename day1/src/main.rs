@@ -1,86 +1,382 @@
-use std::cmp::Ordering;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-fn part1() -> u16{
-    let mut last_depth:Option<u16> = None;
-    let mut increases = 0;
-
-    let filename = "input.txt";
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    for line in reader.lines() {
-        let line = line.unwrap();
-
-        let current_depth: u16 = line.trim().parse().expect("Input was not a number");
-
-        if last_depth.is_none(){
-            println!("{} (N/A - no previous measurement)", current_depth)
-        } else {
-            match current_depth.cmp(&last_depth.unwrap()) {
-                Ordering::Less => println!("{} (decreased)", current_depth),
-                Ordering::Greater => {
-                    println!("{} (increased)", current_depth);
-                    increases = increases + 1;
-                },
-                Ordering::Equal => println!("{} (equal)", current_depth),
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::time::Instant;
+
+use aoc_core::cli::{self, Args};
+use aoc_core::json::Json;
+use aoc_core::output::{run_part, Format, Record};
+use aoc_core::{follow_input, info, log, parse_value, read_input, AocError, Result};
+
+use day1::plot::{self, Series};
+use day1::{
+    count_filtered, parse_recording, report, Channel, Combine, Day1, Depth, Event, ExponentialSmoothing,
+    Filter, MovingAverage, MovingMedian, MovingSum, Tracker,
+};
+
+/// What to do with the filtered depths.
+#[derive(Copy, Clone, PartialEq)]
+enum Mode {
+    /// Print how many times the filtered value increases.
+    Count,
+    /// Print every change as the input grows.
+    Follow,
+    /// Print the trends of the filtered values.
+    Report,
+    /// Draw the depths and their window sums.
+    Plot,
+}
+
+/// The filters `--filter` chooses from.
+#[derive(Copy, Clone, PartialEq)]
+enum FilterKind {
+    Sum,
+    Average,
+    Median,
+    Exponential,
+}
+
+/// Everything the command line asks for, checked to make sense together.
+struct Options {
+    mode: Option<Mode>,
+    filter: Option<FilterKind>,
+    window: Option<usize>,
+    alpha: Option<f64>,
+    width: Option<usize>,
+    svg_file: Option<String>,
+    channel: Option<String>,
+    combine: Option<Combine>,
+    format: Format,
+}
+
+impl Options {
+    /// Whether only the answers to both parts are wanted.
+    fn is_default(&self) -> bool {
+        return self.mode.is_none() && self.filter.is_none() && self.window.is_none();
+    }
+
+    fn mode(&self) -> Mode {
+        return self.mode.unwrap_or(Mode::Count);
+    }
+}
+
+fn filter_arg(args: &mut Args) -> Result<Option<FilterKind>> {
+    return match args.value("--filter")?.as_deref() {
+        None => Ok(None),
+        Some("sum") => Ok(Some(FilterKind::Sum)),
+        Some("average") => Ok(Some(FilterKind::Average)),
+        Some("median") => Ok(Some(FilterKind::Median)),
+        Some("exponential") => Ok(Some(FilterKind::Exponential)),
+        Some(other) => Err(AocError::Usage(format!(
+            "invalid filter {}, expected sum, average, median or exponential", other
+        ))),
+    };
+}
+
+fn window_arg(args: &mut Args) -> Result<Option<usize>> {
+    return match args.value("--window")? {
+        Some(window) => match window.parse() {
+            Ok(0) | Err(_) => Err(AocError::Usage(format!("invalid window {}, expected a size of at least 1", window))),
+            Ok(window) => Ok(Some(window)),
+        },
+        None => Ok(None),
+    };
+}
+
+fn width_arg(args: &mut Args) -> Result<Option<usize>> {
+    return match args.value("--width")? {
+        Some(width) => match width.parse() {
+            Ok(0) | Err(_) => Err(AocError::Usage(format!("invalid width {}, expected a positive number", width))),
+            Ok(width) => Ok(Some(width)),
+        },
+        None => Ok(None),
+    };
+}
+
+fn alpha_arg(args: &mut Args) -> Result<Option<f64>> {
+    return match args.value("--alpha")? {
+        Some(alpha) => match alpha.parse() {
+            Ok(value) if value > 0.0 && value <= 1.0 => Ok(Some(value)),
+            _ => Err(AocError::Usage(format!("invalid alpha {}, expected a number in (0, 1]", alpha))),
+        },
+        None => Ok(None),
+    };
+}
+
+fn combine_arg(args: &mut Args) -> Result<Option<Combine>> {
+    return match args.value("--combine")? {
+        Some(name) => match Combine::from_name(&name) {
+            Some(combine) => Ok(Some(combine)),
+            None => Err(AocError::Usage(format!("invalid combination {}, expected min, max or avg", name))),
+        },
+        None => Ok(None),
+    };
+}
+
+fn options(args: &mut Args) -> Result<Options> {
+    let modes: Vec<Mode> = [("--follow", Mode::Follow), ("--report", Mode::Report), ("--plot", Mode::Plot)]
+        .into_iter()
+        .filter(|(flag, _)| args.flag(flag))
+        .map(|(_, mode)| mode)
+        .collect();
+    if modes.len() > 1 {
+        return Err(AocError::usage("only one of --follow, --report and --plot can be given"));
+    }
+    let options = Options {
+        mode: modes.first().copied(),
+        filter: filter_arg(args)?,
+        window: window_arg(args)?,
+        alpha: alpha_arg(args)?,
+        width: width_arg(args)?,
+        svg_file: args.value("--svg")?,
+        channel: args.value("--channel")?,
+        combine: combine_arg(args)?,
+        format: Format::from_args(args)?,
+    };
+
+    let is_exponential = options.filter == Some(FilterKind::Exponential);
+    if options.alpha.is_some() && !is_exponential {
+        return Err(AocError::usage("--alpha only applies to the exponential filter"));
+    }
+    if options.window.is_some() && is_exponential {
+        return Err(AocError::usage("--window does not apply to the exponential filter"));
+    }
+    if (options.width.is_some() || options.svg_file.is_some()) && options.mode != Some(Mode::Plot) {
+        return Err(AocError::usage("--width and --svg only apply to --plot"));
+    }
+    if matches!(options.mode, Some(Mode::Report) | Some(Mode::Plot)) && options.filter.is_some_and(|f| f != FilterKind::Sum) {
+        return Err(AocError::usage("--report and --plot only support the sum filter"));
+    }
+    if options.channel.is_some() && options.combine.is_some() {
+        return Err(AocError::usage("--channel and --combine cannot be combined"));
+    }
+    if (options.channel.is_some() || options.combine.is_some()) && options.mode == Some(Mode::Follow) {
+        return Err(AocError::usage("--channel and --combine do not apply to --follow"));
+    }
+    return Ok(options);
+}
+
+/// Whether `filename` is a recording, going by its extension, or the options
+/// only make sense for one.
+fn expect_header(filename: &str, options: &Options) -> bool {
+    let extension = Path::new(filename).extension().and_then(|e| e.to_str()).unwrap_or("");
+    let is_table = extension.eq_ignore_ascii_case("csv") || extension.eq_ignore_ascii_case("tsv");
+    return is_table || options.channel.is_some() || options.combine.is_some();
+}
+
+/// The channels to analyse: the one named by `--channel`, the combination
+/// asked by `--combine`, or all of them.
+fn select_channels(input: &str, filename: &str, options: &Options) -> Result<(bool, Vec<Channel>)> {
+    let recording = parse_recording(input, expect_header(filename, options))?;
+    if let Some(name) = &options.channel {
+        let channel = recording.channel(name).ok_or_else(|| AocError::Usage(format!(
+            "no channel named {}, expected one of {}", name, recording.names().join(", ")
+        )))?;
+        return Ok((recording.labelled, vec![channel.clone()]));
+    }
+    if let Some(combine) = options.combine {
+        return Ok((recording.labelled, vec![recording.combine(combine)]));
+    }
+    return Ok((recording.labelled, recording.channels));
+}
+
+/// Runs and times `part` of the puzzle on a channel of a recording.
+fn run_channel_part(channel: &Channel, part: u8) -> Result<Record> {
+    let window = if part == 1 { 1 } else { 3 };
+    let start = Instant::now();
+    let answer = channel.analyse(|depths| count_filtered(depths, MovingSum::new(window)))?;
+    return Ok(Record {
+        day: 1,
+        channel: Some(channel.name.clone()),
+        part: part,
+        answer: answer.to_string(),
+        elapsed: start.elapsed(),
+    });
+}
+
+fn count_with<F: Filter<Input = Depth>>(channel: &Channel, filter: F) -> Result<(String, usize)> {
+    let describe = filter.describe();
+    return channel.analyse(|depths| count_filtered(depths, filter)).map(|count| (describe, count));
+}
+
+/// Prints how many times the filtered depths of `channel` increase, under
+/// its name if `label`.
+fn print_count(channel: &Channel, label: bool, options: &Options) -> Result<()> {
+    let window = options.window.unwrap_or(1);
+    let start = Instant::now();
+    let (describe, increases) = match options.filter.unwrap_or(FilterKind::Sum) {
+        FilterKind::Sum => count_with(channel, MovingSum::new(window))?,
+        FilterKind::Average => count_with(channel, MovingAverage::new(window))?,
+        FilterKind::Median => count_with(channel, MovingMedian::new(window))?,
+        FilterKind::Exponential => count_with(channel, ExponentialSmoothing::new(options.alpha.unwrap_or(0.5)))?,
+    };
+
+    let prefix = if label { format!("{} ", channel.name) } else { String::new() };
+    match options.format {
+        Format::Text => println!("Day 1 {}{}: {}", prefix, describe, increases),
+        Format::Json => {
+            let mut entries = vec![("day", Json::from(1u64))];
+            if label {
+                entries.push(("channel", Json::from(channel.name.as_str())));
             }
+            entries.push(("filter", Json::from(describe.as_str())));
+            entries.push(("answer", Json::from(increases.to_string().as_str())));
+            entries.push(("elapsed_ms", Json::from(start.elapsed().as_secs_f64() * 1000.0)));
+            println!("{}", Json::object(entries));
+        },
+    }
+    return Ok(());
+}
+
+/// Prints the trends of the window sums of `channel`, under its name if
+/// `label`.
+fn print_report(channel: &Channel, label: bool, options: &Options) -> Result<()> {
+    let report = channel.analyse(|depths| report(depths, options.window.unwrap_or(1)))?;
+    match (options.format, label) {
+        (Format::Text, true) => println!("channel: {}\n{}", channel.name, report),
+        (Format::Text, false) => println!("{}", report),
+        (Format::Json, true) => println!("{}", Json::object(vec![
+            ("channel", Json::from(channel.name.as_str())),
+            ("report", report.to_json()),
+        ])),
+        (Format::Json, false) => println!("{}", report.to_json()),
+    }
+    return Ok(());
+}
+
+/// Draws the depths of every channel and their window sums: as sparklines on
+/// standard output, coloured if it is a terminal, or as an SVG chart written
+/// to `--svg`.
+fn print_plot(channels: &[Channel], label: bool, options: &Options) -> Result<()> {
+    let mut series: Vec<Series> = Vec::new();
+    for channel in channels {
+        for window in [1, options.window.unwrap_or(3)] {
+            let mut channel_series = channel.analyse(|depths| plot::series(depths, window))?;
+            channel_series.offset_lines(channel.first_line - 1);
+            if label {
+                channel_series.label = format!("{} {}", channel.name, channel_series.label);
+            }
+            series.push(channel_series);
         }
-        last_depth = Some(current_depth);
     }
-    println!("Increased a total of {} times", increases);
-    return increases;
+
+    match &options.svg_file {
+        Some(svg_file) => {
+            fs::write(svg_file, plot::svg(&series, options.width.unwrap_or(800)))
+                .map_err(|e| AocError::Io { file: svg_file.to_string(), source: e })?;
+            info!("Wrote {}", svg_file);
+        },
+        None => {
+            let colour = io::stdout().is_terminal();
+            for series in &series {
+                let (min, max) = series.range().unwrap_or((0, 0));
+                println!("{} ({} to {})", series.label, min, max);
+                println!("{}", plot::sparkline(series, options.width.unwrap_or(80), colour));
+            }
+        },
+    }
+    return Ok(());
 }
 
-fn sum_n_last_values(history: &Vec<u16>, nvalues: &u16) -> u16{
-    let mut sum = 0;
-    for n in 1..(*nvalues + 1) {
-        let idx = usize::from(n);
-        sum += history[history.len() - idx];
+fn print_event<T: Copy + std::fmt::Display + Into<Json>>(event: &Event<T>, format: Format) {
+    match format {
+        Format::Text => println!(
+            "Line {}: {} ({}) increases={} decreases={}",
+            event.line,
+            event.value,
+            event.change.map_or("N/A", |change| change.name()),
+            event.increases,
+            event.decreases,
+        ),
+        Format::Json => println!("{}", Json::object(vec![
+            ("line", Json::from(event.line as u64)),
+            ("value", event.value.into()),
+            ("change", event.change.map_or(Json::Null, |change| Json::from(change.name()))),
+            ("increases", Json::from(event.increases as u64)),
+            ("decreases", Json::from(event.decreases as u64)),
+        ])),
     }
-    return sum;
 }
 
-fn part2() -> u16{
-    let window_size: u16 = 3;
-    let filename = "input.txt";
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
+/// Reads depths as they are appended to `filename` and prints an event for
+/// every value of `filter`. Only the filter's state is kept in memory.
+fn follow_with<F>(filename: &str, filter: F, format: Format) -> Result<()>
+        where F: Filter<Input = Depth>, F::Value: Into<Json> {
+    let mut tracker = Tracker::new(filter);
+    for (idx, line) in follow_input(filename)?.enumerate() {
+        let depth = parse_value(&line?, idx + 1)?;
+        if let Some(event) = tracker.push(depth, idx + 1)? {
+            print_event(&event, format);
+        }
+    }
+    return Ok(());
+}
 
-    let mut history: Vec<u16> = Vec::new();
-    let mut increases = 0;
+fn follow(filename: &str, options: &Options) -> Result<()> {
+    let window = options.window.unwrap_or(1);
+    return match options.filter.unwrap_or(FilterKind::Sum) {
+        FilterKind::Sum => follow_with(filename, MovingSum::<Depth>::new(window), options.format),
+        FilterKind::Average => follow_with(filename, MovingAverage::new(window), options.format),
+        FilterKind::Median => follow_with(filename, MovingMedian::new(window), options.format),
+        FilterKind::Exponential => {
+            follow_with(filename, ExponentialSmoothing::new(options.alpha.unwrap_or(0.5)), options.format)
+        },
+    };
+}
 
-    for line in reader.lines() {
-        let line = line.unwrap();
-        let value: u16 = line.trim().parse().expect("Input was not a number");
+fn run(filename: &str, options: &Options) -> Result<()> {
+    if options.mode() == Mode::Follow {
+        return follow(filename, options);
+    }
 
-        if history.len() < window_size.into() {
-            history.push(value);
-            continue;
+    let input = read_input(filename)?;
+    let (labelled, channels) = select_channels(&input, filename, options)?;
+    if options.is_default() && !labelled {
+        for part in [1, 2] {
+            run_part(&Day1, part, &input)?.print(options.format);
         }
-        let last_window = sum_n_last_values(&history, &window_size);
-        history.push(value);
-        let current_window = sum_n_last_values(&history, &window_size);
-
-        match current_window.cmp(&last_window) {
-            Ordering::Less => println!("{} (decreased)", value),
-            Ordering::Greater => {
-                println!("{} (increased)", value);
-                increases = increases + 1;
-            },
-            Ordering::Equal => println!("{} (equal)", value),
+        return Ok(());
+    }
+    if options.is_default() {
+        for channel in &channels {
+            for part in [1, 2] {
+                run_channel_part(channel, part)?.print(options.format);
+            }
         }
+        return Ok(());
     }
 
-    println!("Increases {} times", increases);
-    return increases;
+    if options.mode() == Mode::Plot {
+        return print_plot(&channels, labelled, options);
+    }
+    for channel in &channels {
+        match options.mode() {
+            Mode::Report => print_report(channel, labelled, options)?,
+            _ => print_count(channel, labelled, options)?,
+        }
+    }
+    return Ok(());
 }
 
+/// `day1 [--follow|--report|--plot] [--filter sum|average|median|exponential]
+/// [--window N] [--alpha A] [--width N] [--svg FILE] [--channel NAME |
+/// --combine min|max|avg] [--format text|json] [-v|-q] [PATH|-]`: both parts,
+/// or how many times the filtered depths increase, their trends, a plot of
+/// the depths and their window sums, or, following the input as it grows,
+/// every change. A recording with several channels is analysed channel by
+/// channel unless one is chosen or they are combined; a header with a single
+/// column is only read from a `.csv` or `.tsv` file or with `--channel` or
+/// `--combine`. Filters use a window of
+/// 1 by default, the plot a window of 3, and exponential smoothing an alpha
+/// of 0.5.
 fn main() {
-    let increases1 = part1();
-    let increases2 = part2();
-    
-    println!("Solution for part1 is {}", increases1);
-    println!("Solution for part2 is {}", increases2);
+    let mut args = Args::from_env();
+    log::init(&mut args).unwrap_or_else(|e| cli::exit_with_error(&e));
+    let options = options(&mut args).unwrap_or_else(|e| cli::exit_with_error(&e));
+    let filename = args.input("input.txt");
+    args.finish().unwrap_or_else(|e| cli::exit_with_error(&e));
+
+    if let Err(error) = run(&filename, &options) {
+        cli::exit_with_error(&error.with_file(&filename));
+    }
 }
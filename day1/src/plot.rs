@@ -0,0 +1,183 @@
+//! Depth profiles: a series of depths or window sums drawn as a terminal
+//! sparkline or as an SVG chart, each step coloured by how it changed.
+
+use std::fmt::Write;
+
+use aoc_core::Result;
+
+use crate::{Change, Depth, MovingSum, Tracker};
+
+/// A value of a series, produced when the depth at `line` was read, and how
+/// it compares to the previous value.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Point {
+    pub line: usize,
+    pub value: Depth,
+    pub change: Option<Change>,
+}
+
+/// The values to draw, with the label to draw them under.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    pub label: String,
+    pub points: Vec<Point>,
+}
+
+impl Series {
+    /// The smallest and largest values, `None` for an empty series.
+    pub fn range(&self) -> Option<(Depth, Depth)> {
+        let min = self.points.iter().map(|p| p.value).min()?;
+        let max = self.points.iter().map(|p| p.value).max()?;
+        return Some((min, max));
+    }
+
+    /// Moves the points `offset` lines down, for depths that were not read
+    /// from the first line.
+    pub fn offset_lines(&mut self, offset: usize) {
+        for point in &mut self.points {
+            point.line = point.line + offset;
+        }
+    }
+}
+
+/// The sums of `window` consecutive depths, classified as `count_increases`
+/// does; with a window of 1, the depths themselves. Panics if `window` is 0.
+pub fn series(depths: &[Depth], window: usize) -> Result<Series> {
+    let mut tracker = Tracker::new(MovingSum::new(window));
+    let mut points: Vec<Point> = Vec::new();
+    for (idx, &depth) in depths.iter().enumerate() {
+        if let Some(event) = tracker.push(depth, idx + 1)? {
+            points.push(Point { line: event.line, value: event.value, change: event.change });
+        }
+    }
+
+    let label = match window {
+        1 => "depth".to_string(),
+        _ => format!("sum of a window of {}", window),
+    };
+    return Ok(Series { label: label, points: points });
+}
+
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+const ANSI_INCREASE: &str = "\x1b[32m";
+const ANSI_DECREASE: &str = "\x1b[31m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// Where `value` falls between `min` and `max`, from 0 to 1.
+fn scale(value: f64, min: Depth, max: Depth) -> f64 {
+    if max == min {
+        return 0.0;
+    }
+    return (value - min as f64) / (max as f64 - min as f64);
+}
+
+/// `series` in at most `width` columns, deeper values drawn taller. Each
+/// column shows the mean of its share of the points and, with `colour`, is
+/// green if they mostly increased and red if they mostly decreased.
+pub fn sparkline(series: &Series, width: usize, colour: bool) -> String {
+    let (min, max) = match series.range() {
+        Some(range) => range,
+        None => return String::new(),
+    };
+    let points = &series.points;
+    let columns = width.clamp(1, points.len());
+
+    let mut line = String::new();
+    for column in 0..columns {
+        let bucket = &points[column * points.len() / columns..(column + 1) * points.len() / columns];
+        let mean = bucket.iter().map(|p| p.value as f64).sum::<f64>() / bucket.len() as f64;
+        let bar = BARS[(scale(mean, min, max) * (BARS.len() - 1) as f64).round() as usize];
+
+        let increases = bucket.iter().filter(|p| p.change == Some(Change::Increased)).count();
+        let decreases = bucket.iter().filter(|p| p.change == Some(Change::Decreased)).count();
+        if colour && increases > decreases {
+            write!(line, "{}{}{}", ANSI_INCREASE, bar, ANSI_RESET).unwrap();
+        } else if colour && decreases > increases {
+            write!(line, "{}{}{}", ANSI_DECREASE, bar, ANSI_RESET).unwrap();
+        } else {
+            line.push(bar);
+        }
+    }
+    return line;
+}
+
+/// `text` with the characters that are markup in SVG replaced by entities.
+fn escape(text: &str) -> String {
+    return text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+}
+
+const SVG_MARGIN: usize = 40;
+const SVG_PANEL_HEIGHT: usize = 200;
+const SVG_INCREASE: &str = "#1f77b4";
+const SVG_DECREASE: &str = "#ff7f0e";
+const SVG_EQUAL: &str = "#7f7f7f";
+
+/// An SVG chart `width` pixels wide with a panel per series, one above the
+/// other. The lines of the input run along the x axis, shared by all panels,
+/// and depth grows downwards. Each step is a segment coloured by its change.
+pub fn svg(series: &[Series], width: usize) -> String {
+    let first_line = series.iter().filter_map(|s| s.points.first()).map(|p| p.line).min().unwrap_or(1);
+    let last_line = series.iter().filter_map(|s| s.points.last()).map(|p| p.line).max().unwrap_or(1);
+    let plot_width = width.saturating_sub(2 * SVG_MARGIN).max(1) as f64;
+    let x = |line: usize| match last_line > first_line {
+        true => SVG_MARGIN as f64 + (line - first_line) as f64 / (last_line - first_line) as f64 * plot_width,
+        false => SVG_MARGIN as f64,
+    };
+    let height = series.len() * (SVG_PANEL_HEIGHT + SVG_MARGIN) + SVG_MARGIN;
+
+    let mut svg = String::new();
+    writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-family="sans-serif" font-size="12">"#, width, height).unwrap();
+    writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#).unwrap();
+    writeln!(
+        svg,
+        r#"<text x="{}" y="{}"><tspan fill="{}">increase</tspan> <tspan fill="{}">decrease</tspan> <tspan fill="{}">equal</tspan></text>"#,
+        SVG_MARGIN, SVG_MARGIN / 2, SVG_INCREASE, SVG_DECREASE, SVG_EQUAL
+    ).unwrap();
+
+    for (idx, series) in series.iter().enumerate() {
+        let top = SVG_MARGIN + idx * (SVG_PANEL_HEIGHT + SVG_MARGIN);
+        let (min, max) = match series.range() {
+            Some(range) => range,
+            None => continue,
+        };
+        let y = |value: Depth| top as f64 + scale(value as f64, min, max) * SVG_PANEL_HEIGHT as f64;
+
+        writeln!(svg, r#"<text x="{}" y="{}">{} ({} to {}, lines {} to {})</text>"#,
+            SVG_MARGIN, top - 4, escape(&series.label), min, max, first_line, last_line).unwrap();
+        writeln!(svg, r##"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="#cccccc"/>"##,
+            SVG_MARGIN, top, plot_width, SVG_PANEL_HEIGHT).unwrap();
+        for pair in series.points.windows(2) {
+            let colour = match pair[1].change {
+                Some(Change::Increased) => SVG_INCREASE,
+                Some(Change::Decreased) => SVG_DECREASE,
+                _ => SVG_EQUAL,
+            };
+            writeln!(svg, r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="{}"/>"#,
+                x(pair[0].line), y(pair[0].value), x(pair[1].line), y(pair[1].value), colour).unwrap();
+        }
+    }
+    svg.push_str("</svg>\n");
+    return svg;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_are_escaped() {
+        let mut depths = series(&[3, 1, 4], 1).unwrap();
+        depths.label = "a<b&c> depth".to_string();
+        let svg = svg(&[depths], 400);
+        assert!(svg.contains(">a&lt;b&amp;c&gt; depth (1 to 4, lines 1 to 3)</text>"), "{}", svg);
+    }
+
+    #[test]
+    fn lines_are_those_of_the_input() {
+        let mut sums = series(&[3, 1, 4, 1], 2).unwrap();
+        assert_eq!(sums.points.iter().map(|p| p.line).collect::<Vec<_>>(), vec![2, 3, 4]);
+        sums.offset_lines(1);
+        assert_eq!(sums.points.iter().map(|p| p.line).collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert!(svg(&[sums], 400).contains("lines 3 to 5"));
+    }
+}
@@ -0,0 +1,217 @@
+//! Trends of a depth series beyond the count of increases: runs, extremes
+//! and jumps, gathered from the same events the count comes from.
+
+use std::fmt;
+
+use aoc_core::json::Json;
+use aoc_core::Result;
+
+use crate::{Change, Depth, Event, MovingSum, Tracker};
+
+/// Consecutive changes in the same direction, from the value at index
+/// `start` of the series to the one at `end`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Run {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Run {
+    /// Number of changes in the run.
+    pub fn steps(&self) -> usize {
+        return self.end - self.start;
+    }
+
+    fn to_json(self) -> Json {
+        return Json::object(vec![
+            ("start", Json::from(self.start as u64)),
+            ("end", Json::from(self.end as u64)),
+            ("steps", Json::from(self.steps() as u64)),
+        ]);
+    }
+}
+
+/// A single step of the series, from `from` at index `start` to `to` at
+/// `start + 1`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Jump {
+    pub start: usize,
+    pub from: Depth,
+    pub to: Depth,
+}
+
+impl Jump {
+    /// The size of the step, negative when the series goes down. Wide enough
+    /// for any two depths.
+    pub fn change(&self) -> i128 {
+        return self.to as i128 - self.from as i128;
+    }
+}
+
+/// Summary of a series of window sums; with a window of 1, of the depths
+/// themselves. Indices count the values of the series from 0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub window: usize,
+    pub samples: usize,
+    pub increased: usize,
+    pub decreased: usize,
+    pub equal: usize,
+    pub longest_increase: Option<Run>,
+    pub longest_decrease: Option<Run>,
+    pub min: Option<Depth>,
+    pub max: Option<Depth>,
+    pub mean: Option<f64>,
+    pub biggest_jump: Option<Jump>,
+}
+
+/// Builds a `Report` from the events of a `Tracker`, one at a time, keeping
+/// only the current run and the previous value.
+pub struct Trends {
+    report: Report,
+    total: i128,
+    last: Option<Depth>,
+    run: Option<(Change, Run)>,
+}
+
+impl Trends {
+    pub fn new(window: usize) -> Trends {
+        return Trends {
+            report: Report {
+                window: window,
+                samples: 0,
+                increased: 0,
+                decreased: 0,
+                equal: 0,
+                longest_increase: None,
+                longest_decrease: None,
+                min: None,
+                max: None,
+                mean: None,
+                biggest_jump: None,
+            },
+            total: 0,
+            last: None,
+            run: None,
+        };
+    }
+
+    pub fn push(&mut self, event: &Event<Depth>) {
+        let index = self.report.samples;
+        let report = &mut self.report;
+        report.samples = report.samples + 1;
+        report.min = Some(report.min.map_or(event.value, |min| min.min(event.value)));
+        report.max = Some(report.max.map_or(event.value, |max| max.max(event.value)));
+        self.total = self.total + event.value as i128;
+
+        if let Some(last) = self.last {
+            let jump = Jump { start: index - 1, from: last, to: event.value };
+            if report.biggest_jump.is_none_or(|biggest| jump.change().abs() > biggest.change().abs()) {
+                report.biggest_jump = Some(jump);
+            }
+        }
+        self.last = Some(event.value);
+
+        let change = match event.change {
+            Some(change) => change,
+            None => return,
+        };
+        match change {
+            Change::Increased => report.increased = report.increased + 1,
+            Change::Decreased => report.decreased = report.decreased + 1,
+            Change::Equal => report.equal = report.equal + 1,
+        }
+
+        let run = match self.run {
+            Some((run_change, run)) if run_change == change => Run { start: run.start, end: index },
+            _ => Run { start: index - 1, end: index },
+        };
+        self.run = Some((change, run));
+        let longest = match change {
+            Change::Increased => &mut report.longest_increase,
+            Change::Decreased => &mut report.longest_decrease,
+            Change::Equal => return,
+        };
+        if longest.is_none_or(|longest| run.steps() > longest.steps()) {
+            *longest = Some(run);
+        }
+    }
+
+    pub fn report(&self) -> Report {
+        let mut report = self.report.clone();
+        if report.samples > 0 {
+            report.mean = Some(self.total as f64 / report.samples as f64);
+        }
+        return report;
+    }
+}
+
+/// The trends of the sums of `window` consecutive depths. Panics if `window`
+/// is 0, and fails like `count_increases` on overflow.
+pub fn report(depths: &[Depth], window: usize) -> Result<Report> {
+    let mut tracker = Tracker::new(MovingSum::new(window));
+    let mut trends = Trends::new(window);
+    for (idx, &depth) in depths.iter().enumerate() {
+        if let Some(event) = tracker.push(depth, idx + 1)? {
+            trends.push(&event);
+        }
+    }
+    return Ok(trends.report());
+}
+
+fn optional<T>(value: Option<T>, to_json: impl Fn(T) -> Json) -> Json {
+    return value.map_or(Json::Null, to_json);
+}
+
+impl Report {
+    pub fn to_json(&self) -> Json {
+        return Json::object(vec![
+            ("window", Json::from(self.window as u64)),
+            ("samples", Json::from(self.samples as u64)),
+            ("increased", Json::from(self.increased as u64)),
+            ("decreased", Json::from(self.decreased as u64)),
+            ("equal", Json::from(self.equal as u64)),
+            ("longest_increase", optional(self.longest_increase, Run::to_json)),
+            ("longest_decrease", optional(self.longest_decrease, Run::to_json)),
+            ("min", optional(self.min, |min| Json::from(min as f64))),
+            ("max", optional(self.max, |max| Json::from(max as f64))),
+            ("mean", optional(self.mean, Json::from)),
+            ("biggest_jump", optional(self.biggest_jump, |jump| Json::object(vec![
+                ("start", Json::from(jump.start as u64)),
+                ("from", Json::from(jump.from as f64)),
+                ("to", Json::from(jump.to as f64)),
+                ("change", Json::from(jump.change() as f64)),
+            ]))),
+        ]);
+    }
+}
+
+fn write_run(f: &mut fmt::Formatter, name: &str, run: Option<Run>) -> fmt::Result {
+    match run {
+        Some(run) => writeln!(f, "{}: {} steps, from index {} to {}", name, run.steps(), run.start, run.end),
+        None => writeln!(f, "{}: none", name),
+    }
+}
+
+/// One statistic per line.
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "window: {}", self.window)?;
+        writeln!(f, "samples: {}", self.samples)?;
+        writeln!(f, "increased: {}  decreased: {}  equal: {}", self.increased, self.decreased, self.equal)?;
+        write_run(f, "longest increase", self.longest_increase)?;
+        write_run(f, "longest decrease", self.longest_decrease)?;
+        match (self.min, self.max, self.mean) {
+            (Some(min), Some(max), Some(mean)) => writeln!(f, "min: {}  max: {}  mean: {:.3}", min, max, mean)?,
+            _ => writeln!(f, "min: none  max: none  mean: none")?,
+        }
+        match self.biggest_jump {
+            Some(jump) => write!(
+                f,
+                "biggest jump: {:+}, from {} at index {} to {}",
+                jump.change(), jump.from, jump.start, jump.to
+            ),
+            None => write!(f, "biggest jump: none"),
+        }
+    }
+}
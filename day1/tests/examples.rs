@@ -0,0 +1 @@
+aoc_core::example_tests!(day1::Day1);
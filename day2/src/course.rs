@@ -0,0 +1,379 @@
+//! The course language the mission planners write courses in. The puzzle's
+//! `forward`, `up` and `down` lines are valid courses and mean the same:
+//!
+//! ```text
+//! # Survey the ridge twice, then come back.
+//! waypoint start
+//! repeat 2 {
+//!     forward 5   # along the ridge
+//!     down 3
+//! }
+//! turn left
+//! forward 4
+//! turn right
+//! back 2
+//! waypoint end
+//! ```
+//!
+//! A command and its argument share a line, but a line may hold several
+//! commands. `#` comments out the rest of the line.
+
+use aoc_core::{AocError, Result};
+
+use crate::{Command, Step, Turn};
+
+/// The most commands a course may expand to once its blocks are repeated.
+pub const MAX_STEPS: usize = 1_000_000;
+
+/// The most repeat blocks a command may be nested in.
+pub const MAX_NESTING: usize = 64;
+
+/// A named waypoint, `waypoint NAME`. `step` is the number of commands
+/// before it, so it is waypoint `step` of the recorded trajectory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mark {
+    pub name: String,
+    pub line: usize,
+    pub step: usize,
+}
+
+/// The valid commands of a course and the commands that were not.
+#[derive(Debug)]
+pub struct Parsed {
+    /// The commands in the order they run, repeat blocks expanded.
+    pub steps: Vec<Step>,
+    pub marks: Vec<Mark>,
+    /// One parse error per invalid command, in order.
+    pub skipped: Vec<AocError>,
+}
+
+impl Parsed {
+    /// `skipped 2 lines: 3, 7`, or `None` if every line was valid.
+    pub fn summary(&self) -> Option<String> {
+        let mut lines: Vec<usize> = self.skipped.iter().filter_map(|error| match error {
+            AocError::Parse { line, .. } => Some(*line),
+            _ => None,
+        }).collect();
+        lines.sort();
+        lines.dedup();
+        let lines: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+        return match lines.len() {
+            0 => None,
+            1 => Some(format!("skipped 1 line: {}", lines[0])),
+            n => Some(format!("skipped {} lines: {}", n, lines.join(", "))),
+        };
+    }
+}
+
+/// A word, `{` or `}`, and where it starts.
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Token<'a> {
+    text: &'a str,
+    line: usize,
+    column: usize,
+}
+
+impl Token<'_> {
+    fn error(&self, reason: &str) -> AocError {
+        return AocError::parse(self.line, self.text, reason).at_column(self.column);
+    }
+}
+
+/// Splits the course into tokens, dropping whitespace and comments.
+fn tokenize(input: &str) -> Vec<Token<'_>> {
+    let mut tokens: Vec<Token> = Vec::new();
+    for (idx, line) in input.lines().enumerate() {
+        let line = match line.find('#') {
+            Some(comment) => &line[..comment],
+            None => line,
+        };
+        let mut push = |start: usize, end: usize| tokens.push(Token {
+            text: &line[start..end],
+            line: idx + 1,
+            column: line[..start].chars().count() + 1,
+        });
+
+        let mut word: Option<usize> = None;
+        for (at, c) in line.char_indices() {
+            if c.is_whitespace() || c == '{' || c == '}' {
+                if let Some(start) = word.take() {
+                    push(start, at);
+                }
+                if !c.is_whitespace() {
+                    push(at, at + 1);
+                }
+            } else if word.is_none() {
+                word = Some(at);
+            }
+        }
+        if let Some(start) = word {
+            push(start, line.len());
+        }
+    }
+    return tokens;
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    next: usize,
+    marks: Vec<Mark>,
+    errors: Vec<AocError>,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<Token<'a>> {
+        return self.tokens.get(self.next).copied();
+    }
+
+    /// The next token if it is on the same line as `keyword`.
+    fn argument(&mut self, keyword: Token) -> Option<Token<'a>> {
+        let token = self.peek().filter(|t| t.line == keyword.line && t.text != "{" && t.text != "}")?;
+        self.next = self.next + 1;
+        return Some(token);
+    }
+
+    /// Skips a block whose `{` was just read, up to and including the `}`
+    /// closing it, without parsing what is inside.
+    fn skip_block(&mut self) {
+        let mut open = 1;
+        while let Some(token) = self.peek() {
+            self.next = self.next + 1;
+            match token.text {
+                "{" => open = open + 1,
+                "}" if open == 1 => return,
+                "}" => open = open - 1,
+                _ => (),
+            }
+        }
+    }
+
+    /// Skips the rest of the line of an invalid command, up to a `}` that
+    /// may close the block it is in.
+    fn recover(&mut self, line: usize) {
+        while self.peek().is_some_and(|t| t.line == line && t.text != "}") {
+            self.next = self.next + 1;
+        }
+    }
+
+    /// Parses commands into `steps` until the end of the course or, inside a
+    /// block, until the `}` closing it, which is left for the caller.
+    fn block(&mut self, steps: &mut Vec<Step>, depth: usize) {
+        while let Some(token) = self.peek() {
+            if token.text == "}" {
+                if depth > 0 {
+                    return;
+                }
+                self.errors.push(token.error("unmatched }"));
+                self.next = self.next + 1;
+                continue;
+            }
+            self.next = self.next + 1;
+            if let Err(error) = self.command(token, steps, depth) {
+                self.errors.push(error);
+                self.recover(token.line);
+            }
+        }
+    }
+
+    fn command(&mut self, keyword: Token, steps: &mut Vec<Step>, depth: usize) -> Result<()> {
+        let command: fn(i64) -> Command = match keyword.text {
+            "forward" => Command::Forward,
+            "back" => Command::Back,
+            "up" => Command::Up,
+            "down" => Command::Down,
+            "turn" => return self.turn(keyword, steps),
+            "repeat" => return self.repeat(keyword, steps, depth),
+            "waypoint" => return self.waypoint(keyword, steps.len(), depth),
+            _ => return Err(keyword.error("unknown command, expected forward, back, up, down, turn, repeat or waypoint")),
+        };
+        let amount = self.argument(keyword).ok_or(keyword.error("missing amount"))?;
+        let amount = match amount.text.parse::<i64>() {
+            Ok(value) if value < 0 => return Err(amount.error("amount must not be negative")),
+            Ok(value) => value,
+            Err(_) => return Err(amount.error("amount expected to be a whole number")),
+        };
+        steps.push(Step { line: keyword.line, command: command(amount) });
+        return Ok(());
+    }
+
+    fn turn(&mut self, keyword: Token, steps: &mut Vec<Step>) -> Result<()> {
+        let direction = self.argument(keyword).ok_or(keyword.error("missing turn direction"))?;
+        let turn = match direction.text {
+            "left" => Turn::Left,
+            "right" => Turn::Right,
+            _ => return Err(direction.error("expected left or right")),
+        };
+        steps.push(Step { line: keyword.line, command: Command::Turn(turn) });
+        return Ok(());
+    }
+
+    /// `repeat K { ... }`. An invalid count is reported but the block is
+    /// still read, and then run no times, so the rest of the course lines up.
+    fn repeat(&mut self, keyword: Token, steps: &mut Vec<Step>, depth: usize) -> Result<()> {
+        let count = self.argument(keyword).ok_or(keyword.error("missing repeat count"));
+        let open = self.peek().filter(|t| t.text == "{").ok_or(keyword.error("expected { after the repeat count"))?;
+        self.next = self.next + 1;
+        if depth == MAX_NESTING {
+            // Parsing the block would recurse once more per level.
+            self.skip_block();
+            self.errors.push(open.error(&format!("blocks nested more than {} deep", MAX_NESTING)));
+            return Ok(());
+        }
+        let times = match count.and_then(|c| c.text.parse::<usize>().map_err(|_| c.error("repeat count expected to be a whole number"))) {
+            Ok(times) => times,
+            Err(error) => {
+                self.errors.push(error);
+                0
+            },
+        };
+
+        let mut body: Vec<Step> = Vec::new();
+        self.block(&mut body, depth + 1);
+        if self.peek().is_none() {
+            return Err(open.error("block is never closed"));
+        }
+        self.next = self.next + 1;
+
+        let expanded = body.len().checked_mul(times).and_then(|n| n.checked_add(steps.len()));
+        if expanded.is_none_or(|n| n > MAX_STEPS) {
+            return Err(keyword.error(&format!("course expands to more than {} commands", MAX_STEPS)));
+        }
+        if body.is_empty() {
+            // Nothing to run, however many times; the check above lets any
+            // count through.
+            return Ok(());
+        }
+        for _ in 0..times {
+            steps.extend_from_slice(&body);
+        }
+        return Ok(());
+    }
+
+    fn waypoint(&mut self, keyword: Token, step: usize, depth: usize) -> Result<()> {
+        let name = self.argument(keyword).ok_or(keyword.error("missing waypoint name"))?;
+        if depth > 0 {
+            return Err(keyword.error("waypoints cannot be inside a repeat block"));
+        }
+        if self.marks.iter().any(|m| m.name == name.text) {
+            return Err(name.error("waypoint named twice"));
+        }
+        self.marks.push(Mark { name: name.text.to_string(), line: keyword.line, step: step });
+        return Ok(());
+    }
+}
+
+/// Reads the whole course, keeping the valid commands and the reason each
+/// invalid one was rejected, with the line and column it starts at.
+pub fn validate(input: &str) -> Parsed {
+    let mut parser = Parser { tokens: tokenize(input), next: 0, marks: Vec::new(), errors: Vec::new() };
+    let mut steps: Vec<Step> = Vec::new();
+    parser.block(&mut steps, 0);
+    return Parsed { steps: steps, marks: parser.marks, skipped: parser.errors };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The line, column and reason of each error.
+    fn errors(parsed: &Parsed) -> Vec<(usize, usize, String)> {
+        return parsed.skipped.iter().map(|error| match error {
+            AocError::Parse { line, column, reason, .. } => (*line, column.unwrap(), reason.clone()),
+            other => panic!("unexpected error {}", other),
+        }).collect();
+    }
+
+    fn commands(parsed: &Parsed) -> Vec<(usize, Command)> {
+        return parsed.steps.iter().map(|step| (step.line, step.command)).collect();
+    }
+
+    #[test]
+    fn errors_point_at_the_line_and_column() {
+        let parsed = validate("forward 1\n  up x\n\tturn around # comment\n");
+        assert_eq!(errors(&parsed), vec![
+            (2, 6, "amount expected to be a whole number".to_string()),
+            (3, 7, "expected left or right".to_string()),
+        ]);
+        assert_eq!(commands(&parsed), vec![(1, Command::Forward(1))]);
+    }
+
+    #[test]
+    fn recovers_on_the_next_line() {
+        let parsed = validate("dive 3 forward 2\nforward -1\ndown 2\n");
+        assert_eq!(errors(&parsed), vec![
+            (1, 1, "unknown command, expected forward, back, up, down, turn, repeat or waypoint".to_string()),
+            (2, 9, "amount must not be negative".to_string()),
+        ]);
+        assert_eq!(commands(&parsed), vec![(3, Command::Down(2))]);
+        assert_eq!(parsed.summary(), Some("skipped 2 lines: 1, 2".to_string()));
+    }
+
+    #[test]
+    fn repeat_blocks_expand() {
+        let parsed = validate("repeat 2 { forward 1\n  down 2 }\nup 3");
+        assert!(parsed.skipped.is_empty());
+        assert_eq!(commands(&parsed), vec![
+            (1, Command::Forward(1)), (2, Command::Down(2)),
+            (1, Command::Forward(1)), (2, Command::Down(2)),
+            (3, Command::Up(3)),
+        ]);
+    }
+
+    #[test]
+    fn unclosed_block() {
+        let parsed = validate("forward 1\nrepeat 2 {\n  forward 1\n");
+        assert_eq!(errors(&parsed), vec![(2, 10, "block is never closed".to_string())]);
+        assert_eq!(commands(&parsed), vec![(1, Command::Forward(1))]);
+    }
+
+    #[test]
+    fn unmatched_close() {
+        let parsed = validate("forward 1 }\ndown 1\n");
+        assert_eq!(errors(&parsed), vec![(1, 11, "unmatched }".to_string())]);
+        assert_eq!(commands(&parsed), vec![(1, Command::Forward(1)), (2, Command::Down(1))]);
+    }
+
+    #[test]
+    fn steps_are_capped() {
+        let parsed = validate("repeat 1000 { repeat 1000 { forward 1 } }");
+        assert!(parsed.skipped.is_empty());
+        assert_eq!(parsed.steps.len(), MAX_STEPS);
+
+        let parsed = validate("forward 1\nrepeat 1000 {\n  repeat 1000 { forward 1 }\n}\ndown 1");
+        let reason = format!("course expands to more than {} commands", MAX_STEPS);
+        assert_eq!(errors(&parsed), vec![(2, 1, reason)]);
+        assert_eq!(commands(&parsed), vec![(1, Command::Forward(1)), (5, Command::Down(1))]);
+
+        let parsed = validate(&format!("repeat {0} {{ }}\nrepeat {0} {{ repeat 3 {{ }} }}\nup 1", usize::MAX));
+        assert!(parsed.skipped.is_empty());
+        assert_eq!(commands(&parsed), vec![(3, Command::Up(1))]);
+    }
+
+    #[test]
+    fn nesting_is_capped() {
+        let nested = |depth: usize| format!("{}forward 1{}\ndown 1", "repeat 1 {\n".repeat(depth), " }".repeat(depth));
+        let parsed = validate(&nested(MAX_NESTING));
+        assert!(parsed.skipped.is_empty());
+        assert_eq!(parsed.steps.len(), 2);
+
+        // Deep enough to overflow the stack if the parser recursed into it.
+        let parsed = validate(&nested(200_000));
+        let reason = format!("blocks nested more than {} deep", MAX_NESTING);
+        assert_eq!(errors(&parsed), vec![(MAX_NESTING + 1, 10, reason)]);
+        assert_eq!(commands(&parsed), vec![(200_002, Command::Down(1))]);
+    }
+
+    #[test]
+    fn waypoints() {
+        let parsed = validate("waypoint start\nforward 2\nwaypoint ridge\nwaypoint start\nrepeat 2 { waypoint loop }\nwaypoint");
+        assert_eq!(parsed.marks, vec![
+            Mark { name: "start".to_string(), line: 1, step: 0 },
+            Mark { name: "ridge".to_string(), line: 3, step: 1 },
+        ]);
+        assert_eq!(errors(&parsed), vec![
+            (4, 10, "waypoint named twice".to_string()),
+            (5, 12, "waypoints cannot be inside a repeat block".to_string()),
+            (6, 1, "missing waypoint name".to_string()),
+        ]);
+    }
+}
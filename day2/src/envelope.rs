@@ -0,0 +1,163 @@
+//! Safety envelopes: how shallow and deep, how steeply aimed and how far
+//! from the start a course may take the submarine, checked after every
+//! command.
+
+use std::fmt;
+
+use aoc_core::Result;
+
+use crate::{navigate_with, Command, Navigator, Position, Step};
+
+/// One bound of an envelope.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Bound {
+    MinDepth(i64),
+    MaxDepth(i64),
+    /// The largest aim, up or down.
+    MaxAim(i64),
+    /// The furthest from the start in a straight line, ignoring depth.
+    MaxDistance(i64),
+}
+
+impl Bound {
+    fn contains(&self, position: Position, aim: i64) -> bool {
+        return match *self {
+            Bound::MinDepth(min) => position.y >= min,
+            Bound::MaxDepth(max) => position.y <= max,
+            Bound::MaxAim(max) => aim.unsigned_abs() <= max.unsigned_abs(),
+            Bound::MaxDistance(max) => {
+                let (x, z, max) = (position.x as i128, position.z as i128, max as i128);
+                x * x + z * z <= max * max
+            },
+        };
+    }
+}
+
+/// The bounds a course must keep to. Those that are `None` are not checked.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Envelope {
+    pub min_depth: Option<i64>,
+    pub max_depth: Option<i64>,
+    pub max_aim: Option<i64>,
+    pub max_distance: Option<i64>,
+}
+
+impl Envelope {
+    pub fn bounds(&self) -> Vec<Bound> {
+        return [
+            self.min_depth.map(Bound::MinDepth),
+            self.max_depth.map(Bound::MaxDepth),
+            self.max_aim.map(Bound::MaxAim),
+            self.max_distance.map(Bound::MaxDistance),
+        ].into_iter().flatten().collect();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.bounds().is_empty();
+    }
+
+    pub fn contains(&self, position: Position, aim: i64) -> bool {
+        return self.bounds().iter().all(|bound| bound.contains(position, aim));
+    }
+}
+
+/// A command that took the submarine out of the envelope. `step` indexes
+/// the course and `position` and `aim` are where the command left it.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Violation {
+    pub step: usize,
+    pub line: usize,
+    pub command: Command,
+    pub bound: Bound,
+    pub position: Position,
+    pub aim: i64,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "command {} takes ", self.step + 1)?;
+        match self.bound {
+            Bound::MinDepth(0) => write!(f, "the submarine above the surface, to depth {}", self.position.y),
+            Bound::MinDepth(min) => write!(f, "the submarine to depth {}, shallower than the minimum of {}", self.position.y, min),
+            Bound::MaxDepth(max) => write!(f, "the submarine to depth {}, deeper than the maximum of {}", self.position.y, max),
+            Bound::MaxAim(max) => write!(f, "the aim to {}, beyond {} either way", self.aim, max),
+            Bound::MaxDistance(max) => {
+                let distance = (self.position.x as f64).hypot(self.position.z as f64);
+                write!(f, "the submarine {:.1} from the start, beyond the maximum of {}", distance, max)
+            },
+        }
+    }
+}
+
+/// Follows the course with `navigator`, which starts within the envelope,
+/// and returns the commands that take it past one of the bounds. A command
+/// is only reported when the submarine crosses the bound, not for every
+/// command it then stays beyond it.
+pub fn check<N: Navigator>(navigator: N, steps: &[Step], envelope: &Envelope) -> Result<Vec<Violation>> {
+    let bounds = envelope.bounds();
+    let mut outside = vec![false; bounds.len()];
+    let mut violations: Vec<Violation> = Vec::new();
+    navigate_with(navigator, steps, |idx, step, navigator| {
+        let (position, aim) = (navigator.position(), navigator.aim());
+        for (bound, outside) in bounds.iter().zip(outside.iter_mut()) {
+            let inside = bound.contains(position, aim);
+            if !inside && !*outside {
+                violations.push(Violation {
+                    step: idx,
+                    line: step.line,
+                    command: step.command,
+                    bound: *bound,
+                    position: position,
+                    aim: aim,
+                });
+            }
+            *outside = !inside;
+        }
+    })?;
+    return Ok(violations);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, AimNavigator, SimpleNavigator};
+
+    /// The step, line and bound of each violation.
+    fn violations(violations: &[Violation]) -> Vec<(usize, usize, Bound)> {
+        return violations.iter().map(|v| (v.step, v.line, v.bound)).collect();
+    }
+
+    #[test]
+    fn reports_crossings_only() {
+        let steps = parse("down 5\ndown 5\ndown 1\nup 8\ndown 9\nup 20").unwrap();
+        let envelope = Envelope { min_depth: Some(0), max_depth: Some(8), ..Envelope::default() };
+        let found = check(SimpleNavigator::default(), &steps, &envelope).unwrap();
+        assert_eq!(violations(&found), vec![
+            (1, 2, Bound::MaxDepth(8)),
+            (4, 5, Bound::MaxDepth(8)),
+            (5, 6, Bound::MinDepth(0)),
+        ]);
+        assert_eq!(found[0].to_string(), "command 2 takes the submarine to depth 10, deeper than the maximum of 8");
+        assert_eq!(found[2].to_string(), "command 6 takes the submarine above the surface, to depth -8");
+    }
+
+    #[test]
+    fn checks_aim_and_distance() {
+        let steps = parse("down 3\nup 2\nforward 2\nturn left\nforward 2\nup 4\nforward 1").unwrap();
+        let envelope = Envelope { max_aim: Some(2), max_distance: Some(2), ..Envelope::default() };
+        let found = check(AimNavigator::default(), &steps, &envelope).unwrap();
+        assert_eq!(violations(&found), vec![
+            (0, 1, Bound::MaxAim(2)),
+            (4, 5, Bound::MaxDistance(2)),
+            (5, 6, Bound::MaxAim(2)),
+        ]);
+        assert_eq!(found[1].to_string(), "command 5 takes the submarine 2.8 from the start, beyond the maximum of 2");
+        assert_eq!(found[2].aim, -3);
+        assert!(check(SimpleNavigator::default(), &steps, &Envelope::default()).unwrap().is_empty());
+
+        let steps = parse(&format!("up {}\nup 1", i64::MAX)).unwrap();
+        let found = check(AimNavigator::default(), &steps, &Envelope { max_aim: Some(5), ..Envelope::default() }).unwrap();
+        assert_eq!(violations(&found), vec![(0, 1, Bound::MaxAim(5))]);
+        assert_eq!(found[0].aim, -i64::MAX);
+    }
+}
@@ -0,0 +1,279 @@
+//! Day 2: Dive! Follows the submarine's planned course.
+
+use aoc_core::{info, trace, AocError, Result, Solution};
+
+pub mod course;
+pub mod envelope;
+pub mod planner;
+pub mod replay;
+pub mod trajectory;
+
+pub use course::{validate, Mark, Parsed};
+pub use envelope::{check, Bound, Envelope, Violation};
+pub use planner::{plan_part1, plan_part2, Limits};
+pub use replay::Replay;
+pub use trajectory::{diverge, record, Divergence, Trajectory, Waypoint};
+
+
+/// Where the submarine is: `x` is the horizontal position along the starting
+/// heading, `z` the horizontal position to its left and `y` the depth.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Position {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Position {
+    /// The puzzle answer: horizontal position times depth, wide enough for
+    /// any position.
+    pub fn product(&self) -> i128 {
+        return self.x as i128 * self.y as i128;
+    }
+
+    /// Where moving `distance` along `heading` takes the submarine, backwards
+    /// if it is negative. `None` if it leaves the range of `i64`.
+    pub fn advanced(&self, heading: Heading, distance: i64) -> Option<Position> {
+        let mut position = *self;
+        match heading {
+            Heading::East => position.x = self.x.checked_add(distance)?,
+            Heading::North => position.z = self.z.checked_add(distance)?,
+            Heading::West => position.x = self.x.checked_sub(distance)?,
+            Heading::South => position.z = self.z.checked_sub(distance)?,
+        }
+        return Some(position);
+    }
+}
+
+impl std::fmt::Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.z == 0 {
+            return write!(f, "Position[x={},y={}]", self.x, self.y);
+        }
+        write!(f, "Position[x={},y={},z={}]", self.x, self.y, self.z)
+    }
+}
+
+
+/// Which way `turn` rotates the submarine, a quarter turn each time.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+impl std::fmt::Display for Turn {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Turn::Left => write!(f, "left"),
+            Turn::Right => write!(f, "right"),
+        }
+    }
+}
+
+/// Where the submarine faces in the horizontal plane. It starts facing east,
+/// along `x`, and north is to its left, along `z`.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum Heading {
+    #[default]
+    East,
+    North,
+    West,
+    South,
+}
+
+impl Heading {
+    pub fn turned(self, turn: Turn) -> Heading {
+        return match (self, turn) {
+            (Heading::East, Turn::Left) | (Heading::West, Turn::Right) => Heading::North,
+            (Heading::North, Turn::Left) | (Heading::South, Turn::Right) => Heading::West,
+            (Heading::West, Turn::Left) | (Heading::East, Turn::Right) => Heading::South,
+            (Heading::South, Turn::Left) | (Heading::North, Turn::Right) => Heading::East,
+        };
+    }
+}
+
+
+/// A command of the course, such as `forward 5`. The puzzle only uses
+/// `forward`, `up` and `down`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Command {
+    Forward(i64),
+    Back(i64),
+    Up(i64),
+    Down(i64),
+    Turn(Turn),
+}
+
+impl std::fmt::Display for Command {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Command::Forward(amount) => write!(f, "forward {}", amount),
+            Command::Back(amount) => write!(f, "back {}", amount),
+            Command::Up(amount) => write!(f, "up {}", amount),
+            Command::Down(amount) => write!(f, "down {}", amount),
+            Command::Turn(turn) => write!(f, "turn {}", turn),
+        }
+    }
+}
+
+/// A command and the line of the course it was read from.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Step {
+    pub line: usize,
+    pub command: Command,
+}
+
+/// Parses the course, failing on the first invalid line.
+pub fn parse(input: &str) -> Result<Vec<Step>> {
+    let mut parsed = validate(input);
+    if parsed.skipped.is_empty() {
+        return Ok(parsed.steps);
+    }
+    return Err(parsed.skipped.remove(0));
+}
+
+/// A way of moving the submarine in response to the commands.
+pub trait Navigator {
+    /// Moves the submarine, or returns `None` and leaves it where it was if
+    /// the command takes it out of the range of `i64`.
+    fn apply(&mut self, command: Command) -> Option<()>;
+
+    fn position(&self) -> Position;
+
+    /// The aim, for navigators that have one.
+    fn aim(&self) -> i64 {
+        return 0;
+    }
+
+    /// Where the submarine faces, for navigators that can turn.
+    fn heading(&self) -> Heading {
+        return Heading::East;
+    }
+}
+
+/// Part 1: `up` and `down` change the depth directly.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct SimpleNavigator {
+    pub position: Position,
+    pub heading: Heading,
+}
+
+impl Navigator for SimpleNavigator {
+    fn apply(&mut self, command: Command) -> Option<()> {
+        match command {
+            Command::Forward(dx) => self.position = self.position.advanced(self.heading, dx)?,
+            Command::Back(dx) => self.position = self.position.advanced(self.heading, dx.checked_neg()?)?,
+            // Note that up is down, as y is the depth :D
+            Command::Up(dy) => self.position.y = self.position.y.checked_sub(dy)?,
+            Command::Down(dy) => self.position.y = self.position.y.checked_add(dy)?,
+            Command::Turn(turn) => self.heading = self.heading.turned(turn),
+        }
+        return Some(());
+    }
+
+    fn position(&self) -> Position {
+        return self.position;
+    }
+
+    fn heading(&self) -> Heading {
+        return self.heading;
+    }
+}
+
+/// Part 2: `up` and `down` change the aim, `forward` dives along it and
+/// `back` retraces it.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct AimNavigator {
+    pub position: Position,
+    pub aim: i64,
+    pub heading: Heading,
+}
+
+impl AimNavigator {
+    /// Moves `dx` along the heading and `dx` times the aim down.
+    fn dive(&mut self, dx: i64) -> Option<()> {
+        let mut position = self.position.advanced(self.heading, dx)?;
+        position.y = position.y.checked_add(dx.checked_mul(self.aim)?)?;
+        self.position = position;
+        return Some(());
+    }
+}
+
+impl Navigator for AimNavigator {
+    fn apply(&mut self, command: Command) -> Option<()> {
+        match command {
+            Command::Forward(dx) => self.dive(dx)?,
+            Command::Back(dx) => self.dive(dx.checked_neg()?)?,
+            Command::Up(daim) => self.aim = self.aim.checked_sub(daim)?,
+            Command::Down(daim) => self.aim = self.aim.checked_add(daim)?,
+            Command::Turn(turn) => self.heading = self.heading.turned(turn),
+        }
+        return Some(());
+    }
+
+    fn position(&self) -> Position {
+        return self.position;
+    }
+
+    fn aim(&self) -> i64 {
+        return self.aim;
+    }
+
+    fn heading(&self) -> Heading {
+        return self.heading;
+    }
+}
+
+/// Follows the whole course with `navigator`, calling `on_step` with the
+/// index of each step once it is applied, and returns the navigator where it
+/// ended. Fails on the first command that takes the submarine out of range.
+pub fn navigate_with<N, F>(mut navigator: N, steps: &[Step], mut on_step: F) -> Result<N>
+        where N: Navigator, F: FnMut(usize, &Step, &N) {
+    for (idx, step) in steps.iter().enumerate() {
+        if navigator.apply(step.command).is_none() {
+            return Err(AocError::parse(step.line, &step.command.to_string(), "command takes the submarine out of range"));
+        }
+        trace!("{} -> {}, aim {}", step.command, navigator.position(), navigator.aim());
+        on_step(idx, step, &navigator);
+    }
+    info!("{}", navigator.position());
+    return Ok(navigator);
+}
+
+/// Follows the whole course with `navigator`, returning it where it ended.
+pub fn navigate<N: Navigator>(navigator: N, steps: &[Step]) -> Result<N> {
+    return navigate_with(navigator, steps, |_, _, _| ());
+}
+
+/// Product of the final horizontal position and depth, with `up` and `down`
+/// changing the depth directly.
+pub fn part1(steps: &[Step]) -> Result<i128> {
+    return Ok(navigate(SimpleNavigator::default(), steps)?.position().product());
+}
+
+/// Product of the final horizontal position and depth, with `up` and `down`
+/// changing the aim and `forward` diving along it.
+pub fn part2(steps: &[Step]) -> Result<i128> {
+    return Ok(navigate(AimNavigator::default(), steps)?.position().product());
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn day(&self) -> u8 {
+        return 2;
+    }
+
+    fn default_input(&self) -> &'static str {
+        return concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+    }
+
+    fn part1(&self, input: &str) -> Result<String> {
+        return parse(input).and_then(|steps| part1(&steps)).map(|answer| answer.to_string());
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        return parse(input).and_then(|steps| part2(&steps)).map(|answer| answer.to_string());
+    }
+}
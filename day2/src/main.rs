@@ -1,101 +1,472 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::process;
+use std::time::Instant;
 
+use aoc_core::cli::{self, Args};
+use aoc_core::output::{Format, Record};
+use aoc_core::{info, log, read_input, warn, AocError, Result};
 
-struct Position {
-    x: i32,
-    y: i32,
+use day2::{
+    check, diverge, part1, part2, plan_part1, plan_part2, record, validate, AimNavigator, Envelope,
+    Limits, Mark, Parsed, Position, Replay, SimpleNavigator, Step, Trajectory, Waypoint,
+};
+
+/// How `--trajectory` exports the path.
+#[derive(Copy, Clone, PartialEq)]
+enum Export {
+    Csv,
+    Json,
+    Svg,
+}
+
+/// What to do with the course.
+#[derive(Clone, PartialEq)]
+enum Mode {
+    Answers,
+    Trajectory(Export),
+    Waypoints,
+    Plan(i64, i64),
+    Replay,
+    /// Compare with the course in this file.
+    Diff(String),
+}
+
+struct Options {
+    mode: Mode,
+    lenient: bool,
+    part: Option<u8>,
+    output: Option<String>,
+    limits: Limits,
+    envelope: Envelope,
+    reject: bool,
+    format: Format,
+}
+
+fn export_arg(args: &mut Args) -> Result<Option<Export>> {
+    return match args.value("--trajectory")?.as_deref() {
+        None => Ok(None),
+        Some("csv") => Ok(Some(Export::Csv)),
+        Some("json") => Ok(Some(Export::Json)),
+        Some("svg") => Ok(Some(Export::Svg)),
+        Some(other) => Err(AocError::Usage(format!("invalid trajectory format {}, expected csv, json or svg", other))),
+    };
+}
+
+fn part_arg(args: &mut Args) -> Result<Option<u8>> {
+    return match args.value("--part")?.as_deref() {
+        None => Ok(None),
+        Some("1") => Ok(Some(1)),
+        Some("2") => Ok(Some(2)),
+        Some(other) => Err(AocError::Usage(format!("invalid part {}, expected 1 or 2", other))),
+    };
+}
+
+/// The target of `--plan X,DEPTH`.
+fn plan_arg(args: &mut Args) -> Result<Option<(i64, i64)>> {
+    return match args.value("--plan")? {
+        Some(target) => match target.split_once(',').map(|(x, depth)| (x.trim().parse(), depth.trim().parse())) {
+            Some((Ok(x), Ok(depth))) => Ok(Some((x, depth))),
+            _ => Err(AocError::Usage(format!("invalid target {}, expected X,DEPTH", target))),
+        },
+        None => Ok(None),
+    };
+}
+
+fn depth_arg(args: &mut Args, name: &str) -> Result<Option<i64>> {
+    return match args.value(name)? {
+        Some(depth) => match depth.parse() {
+            Ok(value) => Ok(Some(value)),
+            Err(_) => Err(AocError::Usage(format!("invalid {} {}, expected a depth", name, depth))),
+        },
+        None => Ok(None),
+    };
+}
+
+fn limit_arg(args: &mut Args, name: &str, min: i64) -> Result<Option<i64>> {
+    return match args.value(name)? {
+        Some(limit) => match limit.parse() {
+            Ok(value) if value >= min => Ok(Some(value)),
+            _ => Err(AocError::Usage(format!("invalid {} {}, expected a number of at least {}", name, limit, min))),
+        },
+        None => Ok(None),
+    };
 }
 
-impl std::fmt::Display for Position {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "Position[x={},y={}]", self.x, self.y)
+fn options(args: &mut Args) -> Result<Options> {
+    let lenient = match (args.flag("--strict"), args.flag("--lenient")) {
+        (true, true) => return Err(AocError::usage("--strict and --lenient cannot be combined")),
+        (_, lenient) => lenient,
+    };
+    let mut modes: Vec<Mode> = Vec::new();
+    if let Some(export) = export_arg(args)? {
+        modes.push(Mode::Trajectory(export));
+    }
+    if args.flag("--waypoints") {
+        modes.push(Mode::Waypoints);
+    }
+    if let Some((x, depth)) = plan_arg(args)? {
+        modes.push(Mode::Plan(x, depth));
+    }
+    if args.flag("--replay") {
+        modes.push(Mode::Replay);
+    }
+    if let Some(other) = args.value("--diff")? {
+        modes.push(Mode::Diff(other));
+    }
+    if modes.len() > 1 {
+        return Err(AocError::usage("only one of --trajectory, --waypoints, --plan, --replay and --diff can be given"));
     }
+    let mode = modes.pop().unwrap_or(Mode::Answers);
+    let is_plan = matches!(mode, Mode::Plan(..));
+    // A plan keeps to the maximum aim, a course is checked against it.
+    let max_aim = limit_arg(args, "--max-aim", 0)?;
+    let options = Options {
+        mode: mode,
+        lenient: lenient,
+        part: part_arg(args)?,
+        output: args.value("--output")?,
+        limits: Limits {
+            max_aim: if is_plan { max_aim } else { None },
+            max_step: limit_arg(args, "--max-step", 1)?,
+        },
+        envelope: Envelope {
+            min_depth: depth_arg(args, "--min-depth")?,
+            max_depth: depth_arg(args, "--max-depth")?,
+            max_aim: if is_plan { None } else { max_aim },
+            max_distance: limit_arg(args, "--max-distance", 0)?,
+        },
+        reject: args.flag("--reject"),
+        format: Format::from_args(args)?,
+    };
+
+    if options.output.is_some() && !matches!(options.mode, Mode::Trajectory(_)) {
+        return Err(AocError::usage("--output only applies to --trajectory"));
+    }
+    if options.part.is_some() && options.mode == Mode::Answers {
+        return Err(AocError::usage("--part does not apply to the answers, which cover both parts"));
+    }
+    if options.limits.max_step.is_some() && !is_plan {
+        return Err(AocError::usage("--max-step only applies to --plan"));
+    }
+    if is_plan && (options.envelope != Envelope::default() || options.reject) {
+        return Err(AocError::usage("--min-depth, --max-depth, --max-distance and --reject do not apply to --plan"));
+    }
+    if !options.envelope.contains(Position::default(), 0) {
+        return Err(AocError::usage("the envelope must contain the start, at depth 0"));
+    }
+    if options.reject && options.envelope.is_empty() {
+        return Err(AocError::usage("--reject needs a bound to check, such as --max-depth"));
+    }
+    if options.lenient && is_plan {
+        return Err(AocError::usage("--lenient does not apply to --plan, which reads no course"));
+    }
+    return Ok(options);
 }
 
+/// Prints the shortest course to `target` under the rules of `part`, one
+/// command per line.
+fn print_plan(x: i64, depth: i64, part: u8, limits: Limits) -> Result<()> {
+    let course = match part {
+        1 => plan_part1(x, depth, limits)?,
+        _ => plan_part2(x, depth, limits)?,
+    };
+    info!("{} commands", course.len());
+    for command in course {
+        println!("{}", command);
+    }
+    return Ok(());
+}
 
-fn part1() -> i32{
-    let filename = "input";
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
+/// The valid commands and waypoints of the course in `filename`. Invalid
+/// commands are reported with their reason, then skipped if `lenient` and
+/// fatal if not.
+fn load_course(filename: &str, lenient: bool) -> Parsed {
+    let input = read_input(filename).unwrap_or_else(|e| cli::exit_with_error(&e));
+    let mut parsed = validate(&input);
+    let summary = match parsed.summary() {
+        Some(summary) => summary,
+        None => return parsed,
+    };
+    let skipped: Vec<AocError> = parsed.skipped.drain(..).map(|e| e.with_file(filename)).collect();
+    if !lenient {
+        cli::exit_with_errors(&skipped);
+    }
+    for error in &skipped {
+        warn!("{}", error);
+    }
+    warn!("{}", summary);
+    return parsed;
+}
 
-    let mut current_position = Position { x: 0, y: 0 };
+/// Checks the course in `filename` against the envelope under the rules of
+/// each of `parts`. Every command that leaves it is reported, as a warning,
+/// or with `reject`, as an error that stops here.
+fn check_envelope(filename: &str, steps: &[Step], parts: &[u8], envelope: &Envelope, reject: bool) {
+    if envelope.is_empty() {
+        return;
+    }
+    let mut errors: Vec<AocError> = Vec::new();
+    for &part in parts {
+        let violations = match part {
+            1 => check(SimpleNavigator::default(), steps, envelope),
+            _ => check(AimNavigator::default(), steps, envelope),
+        }.unwrap_or_else(|e| cli::exit_with_error(&e.with_file(filename)));
+        errors.extend(violations.iter().map(|violation| {
+            let reason = format!("part {}: {}", part, violation);
+            AocError::parse(violation.line, &violation.command.to_string(), &reason).with_file(filename)
+        }));
+    }
+    if errors.is_empty() {
+        return;
+    }
+    if reject {
+        cli::exit_with_errors(&errors);
+    }
+    for error in &errors {
+        warn!("{}", error);
+    }
+}
 
+/// The answer to one part for a course.
+type Solver = fn(&[Step]) -> Result<i128>;
 
-    for line in  reader.lines() {
-        let line = line.unwrap();
-        let mut split = line.split_whitespace();
-        let direction = split.next().unwrap();
-        let ammount = split.next().unwrap()
-            .parse::<u16>()
-            .expect("Ammount expected to be an integer");
+/// Prints the answers to both parts, timed like the runner times them.
+fn print_answers(steps: &[Step], format: Format) -> Result<()> {
+    let solvers: [Solver; 2] = [part1, part2];
+    for (part, solver) in (1..).zip(solvers) {
+        let start = Instant::now();
+        let answer = solver(steps)?;
+        let record = Record { day: 2, channel: None, part: part, answer: answer.to_string(), elapsed: start.elapsed() };
+        record.print(format);
+    }
+    return Ok(());
+}
 
-        match (direction, ammount) {
-            ("forward", dx) => {
-                current_position.x = current_position.x + dx as i32;
-            },
-            ("up", dy) => {
-                // Note that up is down, as y is the depth :D
-                current_position.y = current_position.y - dy as i32;
-            },
-            ("down", dy) => {
-                current_position.y = current_position.y + dy as i32;
-            }
-            _ => {
-                println!("Invalid command {}", direction);
-            }
+/// The path of `steps` under the rules of `part`.
+fn trajectory(steps: &[Step], part: u8) -> Result<Trajectory> {
+    return match part {
+        1 => record(SimpleNavigator::default(), steps).map(|(_, trajectory)| trajectory),
+        _ => record(AimNavigator::default(), steps).map(|(_, trajectory)| trajectory),
+    };
+}
 
-        }
-        //println!("{} {}", direction, ammount);
+fn describe(waypoint: &Waypoint) -> String {
+    return format!("{}, aim {}", waypoint.position, waypoint.aim);
+}
+
+/// Prints where the submarine is at each named waypoint under the rules of
+/// `part`.
+fn print_marks(steps: &[Step], marks: &[Mark], part: u8) -> Result<()> {
+    if marks.is_empty() {
+        warn!("the course names no waypoints");
+    }
+    let trajectory = trajectory(steps, part)?;
+    for mark in marks {
+        let waypoint = trajectory.waypoints[mark.step];
+        println!("{} (line {}): {}", mark.name, mark.line, describe(&waypoint));
     }
-    
-    println!("{}", current_position);
-    return current_position.x * current_position.y;
+    return Ok(());
 }
 
-fn part2() -> i32{
-    let filename = "input";
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
+/// Records the path of `steps` under the rules of `part` and writes it to
+/// `output`, or standard output.
+fn export_trajectory(steps: &[Step], part: u8, export: Export, output: Option<&str>) -> Result<()> {
+    let trajectory = trajectory(steps, part)?;
+    let contents = match export {
+        Export::Csv => trajectory.to_csv(),
+        Export::Json => format!("{}\n", trajectory.to_json()),
+        Export::Svg => trajectory.to_svg(800, 400),
+    };
 
-    let mut current_position = Position { x: 0, y: 0 };
-    let mut aim: i32 = 0;
+    match output {
+        Some(output) => {
+            fs::write(output, contents).map_err(|e| AocError::Io { file: output.to_string(), source: e })?;
+            info!("Wrote {}", output);
+        },
+        None => print!("{}", contents),
+    }
+    return Ok(());
+}
 
-    for line in  reader.lines() {
-        let line = line.unwrap();
-        let mut split = line.split_whitespace();
-        let direction = split.next().unwrap();
-        let ammount = split.next().unwrap()
-            .parse::<u16>()
-            .expect("Ammount expected to be an integer");
+const REPLAY_HELP: &str = "\
+next [N]      run the next command, or the next N
+back [N]      undo the last command, or the last N
+line N        go to just before the first command on line N
+break DEPTH   make continue stop when a command goes deeper than DEPTH;
+              break alone clears it
+continue      run until the breakpoint or the end of the course
+quit";
 
-        match (direction, ammount) {
-            ("forward", dx) => {
-                current_position.x = current_position.x + dx as i32;
-                current_position.y = current_position.y + dx as i32 * aim; 
+fn print_replay(replay: &Replay) {
+    let state = format!("[{}/{}] {}", replay.at(), replay.len(), describe(&replay.waypoint()));
+    match replay.next_step() {
+        Some(step) => println!("{}; next, line {}: {}", state, step.line, step.command),
+        None => println!("{}; end of course", state),
+    }
+}
+
+/// Steps through `steps` under the rules of `part`, reading debugger
+/// commands from standard input.
+fn replay(steps: &[Step], part: u8) -> Result<()> {
+    let mut replay = match part {
+        1 => Replay::new(SimpleNavigator::default(), steps)?,
+        _ => Replay::new(AimNavigator::default(), steps)?,
+    };
+    let stdin = io::stdin();
+    let interactive = stdin.is_terminal();
+    let read_error = |e| AocError::Io { file: "<stdin>".to_string(), source: e };
+    let mut breakpoint: Option<i64> = None;
+
+    print_replay(&replay);
+    loop {
+        if interactive {
+            print!("> ");
+            io::stdout().flush().map_err(|e| AocError::Io { file: "<stdout>".to_string(), source: e })?;
+        }
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).map_err(read_error)? == 0 {
+            return Ok(());
+        }
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let count = |word: Option<&&str>| word.map_or(Some(1), |w| w.parse::<usize>().ok());
+        match words.as_slice() {
+            [] => continue,
+            ["n" | "next", rest @ ..] if rest.len() <= 1 => match count(rest.first()) {
+                Some(count) => {
+                    replay.forward(count);
+                },
+                None => println!("expected a number of commands"),
             },
-            ("up", daim) => {
-                // Note that up is down, as y is the depth :D
-                aim -= daim as i32;
+            ["b" | "back", rest @ ..] if rest.len() <= 1 => match count(rest.first()) {
+                Some(count) => {
+                    replay.back(count);
+                },
+                None => println!("expected a number of commands"),
+            },
+            ["l" | "line", line] => match line.parse() {
+                Ok(line) if replay.to_line(line) => (),
+                _ => println!("no command on line {}", line),
+            },
+            ["break"] => {
+                breakpoint = None;
+                println!("breakpoint cleared");
+                continue;
+            },
+            ["break", depth] => match depth.parse() {
+                Ok(depth) => {
+                    breakpoint = Some(depth);
+                    println!("continue stops when a command goes deeper than {}", depth);
+                    continue;
+                },
+                Err(_) => println!("expected a depth"),
+            },
+            ["c" | "continue"] => match breakpoint {
+                Some(depth) if replay.run_until_deeper(depth) => println!("deeper than {}", depth),
+                _ => {
+                    replay.forward(usize::MAX);
+                },
+            },
+            ["q" | "quit"] => return Ok(()),
+            ["h" | "help"] => {
+                println!("{}", REPLAY_HELP);
+                continue;
             },
-            ("down", daim) => {
-                aim += daim as i32;
-            }
             _ => {
-                println!("Invalid command {}", direction);
-            }
+                println!("unknown command {:?}, try help", line.trim());
+                continue;
+            },
         }
-        println!("{}", line);
-        println!("{}, {}", current_position, aim);
+        print_replay(&replay);
     }
-    
-    println!("{}", current_position);
-    return current_position.x * current_position.y;
 }
 
+/// One side of `--diff` where the courses part ways.
+fn print_side(filename: &str, steps: &[Step], trajectory: &Trajectory, step: usize, waypoint: Option<Waypoint>) {
+    match waypoint {
+        Some(waypoint) => {
+            let step = &steps[step - 1];
+            println!("  {}:{}: {} -> {}", filename, step.line, step.command, describe(&waypoint));
+        },
+        None => println!("  {}: ended at {}", filename, describe(trajectory.waypoints.last().unwrap())),
+    }
+}
+
+/// Follows both courses under the rules of `part` and reports the first
+/// command after which they are not in the same place, returning whether
+/// they never part ways.
+fn diff(left: (&str, &[Step]), right: (&str, &[Step]), part: u8) -> Result<bool> {
+    let trajectories = (
+        trajectory(left.1, part).map_err(|e| e.with_file(left.0))?,
+        trajectory(right.1, part).map_err(|e| e.with_file(right.0))?,
+    );
+    let divergence = match diverge(&trajectories.0, &trajectories.1) {
+        Some(divergence) => divergence,
+        None => {
+            println!("both courses follow the same path to {}", describe(trajectories.0.waypoints.last().unwrap()));
+            return Ok(true);
+        },
+    };
+    println!("the courses part ways at command {}:", divergence.step);
+    print_side(left.0, left.1, &trajectories.0, divergence.step, divergence.left);
+    print_side(right.0, right.1, &trajectories.1, divergence.step, divergence.right);
+    return Ok(false);
+}
+
+/// `day2 [--strict|--lenient] [MODE] [--part 1|2] [--format text|json]
+/// [-v|-q] [PATH|-]`: both parts, or with a mode, under the rules of one
+/// part, the second by default:
+///
+/// - `--trajectory csv|json|svg [--output FILE]` exports the path followed.
+/// - `--waypoints` prints where it passes each named waypoint.
+/// - `--replay` steps through the course with commands from standard input.
+/// - `--diff OTHER` finds where the course and the one in `OTHER` part ways,
+///   and exits with status 1 if they do.
+/// - `--plan X,DEPTH [--max-aim N] [--max-step N]` prints the shortest course
+///   to the target instead of reading one.
+///
+/// Invalid commands are all reported; `--strict`, the default, then stops and
+/// `--lenient` skips them. `--min-depth N`, `--max-depth N`, `--max-aim N` and
+/// `--max-distance N` set a safety envelope, and every command that leaves it
+/// is reported too, stopping there with `--reject`.
 fn main() {
-    let part1_result = part1();
-    let part2_result = part2();
-    println!("Part 1 result is {}", part1_result);
-    println!("Part 2 result is {}", part2_result);
+    let mut args = Args::from_env();
+    log::init(&mut args).unwrap_or_else(|e| cli::exit_with_error(&e));
+    let options = options(&mut args).unwrap_or_else(|e| cli::exit_with_error(&e));
+    let part = options.part.unwrap_or(2);
+    if let Mode::Plan(x, depth) = options.mode {
+        args.finish().unwrap_or_else(|e| cli::exit_with_error(&e));
+        print_plan(x, depth, part, options.limits).unwrap_or_else(|e| cli::exit_with_error(&e));
+        return;
+    }
+    let filename = args.input("input");
+    args.finish().unwrap_or_else(|e| cli::exit_with_error(&e));
+    if options.mode == Mode::Diff("-".to_string()) && filename == "-" {
+        cli::exit_with_error(&AocError::usage("only one course can be read from standard input"));
+    }
+    if options.mode == Mode::Replay && filename == "-" {
+        cli::exit_with_error(&AocError::usage("--replay reads its commands from standard input, so the course must be a file"));
+    }
+
+    let course = load_course(&filename, options.lenient);
+    let parts: &[u8] = if options.mode == Mode::Answers { &[1, 2] } else { &[part] };
+    check_envelope(&filename, &course.steps, parts, &options.envelope, options.reject);
+    let result = match &options.mode {
+        Mode::Answers => print_answers(&course.steps, options.format),
+        Mode::Trajectory(export) => export_trajectory(&course.steps, part, *export, options.output.as_deref()),
+        Mode::Waypoints => print_marks(&course.steps, &course.marks, part),
+        Mode::Replay => replay(&course.steps, part),
+        Mode::Diff(other) => {
+            let other_course = load_course(other, options.lenient);
+            check_envelope(other, &other_course.steps, parts, &options.envelope, options.reject);
+            let same = diff((&filename, &course.steps), (other, &other_course.steps), part)
+                .unwrap_or_else(|e| cli::exit_with_error(&e));
+            if !same {
+                process::exit(1);
+            }
+            Ok(())
+        },
+        Mode::Plan(..) => unreachable!(),
+    };
+    result.unwrap_or_else(|e| cli::exit_with_error(&e.with_file(&filename)));
 }
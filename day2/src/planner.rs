@@ -0,0 +1,375 @@
+//! Plans a course to a target: the fewest `forward`, `up` and `down`
+//! commands that take the submarine there from the start, under the rules of
+//! either part. Plans never use `back` or `turn`.
+//!
+//! In part 2 a course changes its aim `A` times and moves forward in `F`
+//! commands. Each change of aim by `c` with `r` still to go adds `c * r` to
+//! the final depth, and moving `x` takes at least `ceil(x / max_step)`
+//! forward commands, more if the changes split the way badly. Aiming down by
+//! `depth / x` and then one more for the last `depth % x` always works, so a
+//! plan never needs more than two changes of aim and one extra forward
+//! command, and only those few shapes have to be searched.
+
+use aoc_core::{AocError, Result};
+
+use crate::course::MAX_STEPS;
+use crate::Command;
+
+/// Bounds a planned course keeps to.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Limits {
+    /// The largest aim, up or down. Part 1 has no aim.
+    pub max_aim: Option<i64>,
+    /// The furthest a single command may move the submarine: `forward`, and
+    /// in part 1 `up` and `down`. Must be positive.
+    pub max_step: Option<i64>,
+}
+
+impl Limits {
+    /// How many commands it takes to move `distance`.
+    fn commands(&self, distance: i64) -> i64 {
+        return match self.max_step {
+            _ if distance == 0 => 0,
+            None => 1,
+            Some(step) => (distance - 1) / step + 1,
+        };
+    }
+
+    /// Appends the commands that move `distance`, as long as allowed.
+    fn moves(&self, distance: i64, command: fn(i64) -> Command, course: &mut Vec<Command>) {
+        let step = self.max_step.unwrap_or(distance);
+        let mut left = distance;
+        while left > 0 {
+            let amount = left.min(step);
+            course.push(command(amount));
+            left = left - amount;
+        }
+    }
+}
+
+/// Fails for targets no plan reaches: plans only move forward, so `x` must
+/// not be negative, and the depth is mirrored to plan up or down, so it must
+/// not be `i64::MIN`.
+fn check_target(x: i64, depth: i64) -> Result<()> {
+    if x < 0 {
+        return Err(AocError::no_solution("plans only use forward, up and down, so x cannot be negative"));
+    }
+    if depth == i64::MIN {
+        return Err(AocError::NoSolution(format!("depth {} is out of range", depth)));
+    }
+    return Ok(());
+}
+
+/// Fails if a plan of at least `commands` commands is longer than a course
+/// may be.
+fn check_length(commands: i64) -> Result<()> {
+    if commands > MAX_STEPS as i64 {
+        return Err(AocError::NoSolution(format!("the plan takes more than {} commands", MAX_STEPS)));
+    }
+    return Ok(());
+}
+
+/// The course for part 1: forward, then straight down or up.
+pub fn plan_part1(x: i64, depth: i64, limits: Limits) -> Result<Vec<Command>> {
+    check_target(x, depth)?;
+    check_length(limits.commands(x).saturating_add(limits.commands(depth.abs())))?;
+    let mut course: Vec<Command> = Vec::new();
+    limits.moves(x, Command::Forward, &mut course);
+    if depth > 0 {
+        limits.moves(depth, Command::Down, &mut course);
+    } else {
+        limits.moves(-depth, Command::Up, &mut course);
+    }
+    return Ok(course);
+}
+
+/// A change of aim by `change` after moving forward `at`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct AimChange {
+    at: i64,
+    change: i64,
+}
+
+/// The course for part 2, as short as possible. Without `back` the depth
+/// only changes when moving forward with some aim, so it must be zero if `x`
+/// is.
+pub fn plan_part2(x: i64, depth: i64, limits: Limits) -> Result<Vec<Command>> {
+    check_target(x, depth)?;
+    check_length(limits.commands(x))?;
+    if depth == 0 {
+        return Ok(course(x, &[], limits));
+    }
+    if x == 0 {
+        return Err(AocError::no_solution("plans only use forward, up and down, so the depth only changes moving forward"));
+    }
+    if let Some(max_aim) = limits.max_aim {
+        if depth.unsigned_abs() as i128 > max_aim as i128 * x as i128 {
+            return Err(AocError::NoSolution(format!(
+                "reaching depth {} within {} needs an aim over {}", depth, x, max_aim
+            )));
+        }
+    }
+
+    // Aiming up instead of down mirrors the course above the surface.
+    let changes: Vec<AimChange> = aim_changes(x, depth.abs(), limits)
+        .into_iter()
+        .map(|c| AimChange { at: c.at, change: c.change * depth.signum() })
+        .collect();
+    let course = course(x, &changes, limits);
+    check_length(course.len() as i64)?;
+    return Ok(course);
+}
+
+fn course(x: i64, changes: &[AimChange], limits: Limits) -> Vec<Command> {
+    let mut course: Vec<Command> = Vec::new();
+    let mut done = 0;
+    for change in changes {
+        limits.moves(change.at - done, Command::Forward, &mut course);
+        match change.change > 0 {
+            true => course.push(Command::Down(change.change)),
+            false => course.push(Command::Up(-change.change)),
+        }
+        done = change.at;
+    }
+    limits.moves(x - done, Command::Forward, &mut course);
+    return course;
+}
+
+/// The changes of aim of the shortest course to a positive `depth`, which
+/// is within reach.
+fn aim_changes(x: i64, depth: i64, limits: Limits) -> Vec<AimChange> {
+    let fewest = limits.commands(x);
+    let single = |r: i64| AimChange { at: x - r, change: depth / r };
+    // A single change by `depth / r` with `r` still to go, within the
+    // maximum aim, which is at least 1 as the depth is within reach.
+    let low = limits.max_aim.map_or(1, |max| (depth - 1) / max + 1);
+    let divisors = divisors(depth, low, x.min(depth));
+
+    // One change, without an extra forward command.
+    if let Some(&r) = divisors.iter().find(|&&r| limits.commands(x - r) + limits.commands(r) == fewest) {
+        return vec![single(r)];
+    }
+    // One change with an extra forward command, or two without.
+    if let Some(&r) = divisors.first() {
+        return vec![single(r)];
+    }
+    if let Some(changes) = two_changes(x, depth, limits) {
+        return changes;
+    }
+    // Two changes and an extra forward command.
+    return vec![
+        AimChange { at: 0, change: depth / x },
+        AimChange { at: x - depth % x, change: 1 },
+    ];
+}
+
+/// Two changes of aim that split the way without an extra forward command,
+/// found by whichever search has fewer cases to try. Without a maximum aim a
+/// single change always works, and without a maximum step the single
+/// forward command leaves no room for two.
+fn two_changes(x: i64, depth: i64, limits: Limits) -> Option<Vec<AimChange>> {
+    let (max_aim, step) = (limits.max_aim?, limits.max_step?);
+    let by_aim = (2 * max_aim as i128 + 1).saturating_pow(2).saturating_mul(x.min(step) as i128);
+    if by_aim < x as i128 * x as i128 {
+        return two_changes_by_aim(x, depth, limits, max_aim, step);
+    }
+    return two_changes_by_place(x, depth, limits);
+}
+
+/// Whether changing the aim after moving `first` and `second` takes no more
+/// forward commands than moving `x` in one go.
+fn splits_evenly(x: i64, first: i64, second: i64, limits: Limits) -> bool {
+    let forwards = limits.commands(first) + limits.commands(second - first) + limits.commands(x - second);
+    return forwards == limits.commands(x);
+}
+
+/// Tries every pair of places to change the aim.
+fn two_changes_by_place(x: i64, depth: i64, limits: Limits) -> Option<Vec<AimChange>> {
+    for first in (0..x).filter(|&at| splits_evenly(x, at, at, limits)) {
+        for second in (first + 1..x).filter(|&at| splits_evenly(x, first, at, limits)) {
+            if let Some((c1, c2)) = solve(x - first, x - second, depth, limits.max_aim) {
+                return Some(vec![AimChange { at: first, change: c1 }, AimChange { at: second, change: c2 }]);
+            }
+        }
+    }
+    return None;
+}
+
+/// Tries every pair of aims. The ways still to go when the aim changes,
+/// `r1` and `r2`, then lie on a line, and whether they split the way evenly
+/// repeats every `step` along it.
+fn two_changes_by_aim(x: i64, depth: i64, limits: Limits, max_aim: i64, step: i64) -> Option<Vec<AimChange>> {
+    for c1 in (-max_aim..=max_aim).filter(|&c| c != 0) {
+        'aims: for c2 in (-max_aim - c1..=max_aim - c1).filter(|&c| c != 0) {
+            let (g, u, v) = extended_gcd(c1 as i128, c2 as i128);
+            if depth as i128 % g != 0 {
+                continue;
+            }
+            // Every solution is `r1 + k * b, r2 - k * a` for some `k`.
+            let (r1, r2) = (u * (depth as i128 / g), v * (depth as i128 / g));
+            let (a, b) = (c1 as i128 / g, c2 as i128 / g);
+            // `x >= r1 > r2 >= 1`, each as `offset + k * slope >= 0`.
+            let (mut low, mut high) = (i128::MIN, i128::MAX);
+            for (offset, slope) in [(r2 - 1, -a), (r1 - r2 - 1, a + b), (x as i128 - r1, -b)] {
+                match slope.signum() {
+                    1 => low = low.max(ceil_div(-offset, slope)),
+                    -1 => high = high.min(offset.div_euclid(-slope)),
+                    _ if offset < 0 => continue 'aims,
+                    _ => (),
+                }
+            }
+            for k in (low..=high).take(step as usize) {
+                let (first, second) = ((x as i128 - r1 - k * b) as i64, (x as i128 - r2 + k * a) as i64);
+                if splits_evenly(x, first, second, limits) {
+                    return Some(vec![AimChange { at: first, change: c1 }, AimChange { at: second, change: c2 }]);
+                }
+            }
+        }
+    }
+    return None;
+}
+
+/// `(g, u, v)` such that `u * a + v * b = g`, the greatest common divisor.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        return (a, 1, 0);
+    }
+    let (g, u, v) = extended_gcd(b, a % b);
+    return (g, v, u - (a / b) * v);
+}
+
+/// Non-zero `c1` and `c2` with `c1 * r1 + c2 * r2 = depth` that keep the aim,
+/// `c1` and then `c1 + c2`, within `max_aim`. `r1` is greater than `r2`.
+fn solve(r1: i64, r2: i64, depth: i64, max_aim: Option<i64>) -> Option<(i64, i64)> {
+    let (r1, r2, depth) = (r1 as i128, r2 as i128, depth as i128);
+    let (g, u, v) = extended_gcd(r1, r2);
+    if depth % g != 0 {
+        return None;
+    }
+    // Every solution is `c1 + k * b, c2 - k * a` for some `k`, which moves
+    // the second aim by `k * (b - a)`.
+    let (c1, c2) = (u * (depth / g), v * (depth / g));
+    let (a, b) = (r1 / g, r2 / g);
+    let (low, high) = match max_aim {
+        None => (0, 2),
+        Some(max) => {
+            let (max, level, d) = (max as i128, c1 + c2, a - b);
+            let low = ceil_div(-max - c1, b).max(ceil_div(level - max, d));
+            let high = (max - c1).div_euclid(b).min((level + max).div_euclid(d));
+            (low, high)
+        },
+    };
+    // At most two values of `k` make a change zero.
+    return (low..=high)
+        .take(3)
+        .map(|k| (c1 + k * b, c2 - k * a))
+        .find(|&(c1, c2)| c1 != 0 && c2 != 0)
+        .and_then(|(c1, c2)| Some((i64::try_from(c1).ok()?, i64::try_from(c2).ok()?)));
+}
+
+fn ceil_div(a: i128, b: i128) -> i128 {
+    return -(-a).div_euclid(b);
+}
+
+/// The divisors of `n` from `low` to `high`, largest first, built from the
+/// prime factors of `n`. Even a 63-bit number has only about 100,000.
+fn divisors(n: i64, low: i64, high: i64) -> Vec<i64> {
+    let mut primes: Vec<u64> = Vec::new();
+    factor(n as u64, &mut primes);
+    primes.sort();
+    let mut divisors: Vec<u64> = vec![1];
+    let mut idx = 0;
+    while idx < primes.len() {
+        let prime = primes[idx];
+        let count = primes[idx..].iter().take_while(|&&p| p == prime).count();
+        let mut multiples: Vec<u64> = Vec::new();
+        for &divisor in &divisors {
+            let mut multiple = divisor;
+            for _ in 0..count {
+                multiple = multiple * prime;
+                multiples.push(multiple);
+            }
+        }
+        divisors.extend(multiples);
+        idx = idx + count;
+    }
+    let mut divisors: Vec<i64> = divisors.into_iter().map(|d| d as i64).filter(|&d| d >= low && d <= high).collect();
+    divisors.sort_by(|a, b| b.cmp(a));
+    return divisors;
+}
+
+/// Appends the prime factors of `n`, with repeats, in no particular order.
+fn factor(n: u64, primes: &mut Vec<u64>) {
+    if n == 1 {
+        return;
+    }
+    if is_prime(n) {
+        primes.push(n);
+        return;
+    }
+    let divisor = if n.is_multiple_of(2) { 2 } else { rho(n) };
+    factor(divisor, primes);
+    factor(n / divisor, primes);
+}
+
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    return (a as u128 * b as u128 % modulus as u128) as u64;
+}
+
+fn pow_mod(base: u64, exponent: u64, modulus: u64) -> u64 {
+    let (mut base, mut exponent, mut result) = (base % modulus, exponent, 1);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent = exponent >> 1;
+    }
+    return result;
+}
+
+/// Miller-Rabin, which these bases make exact for every `u64`.
+fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    if let Some(&base) = BASES.iter().find(|&&base| n.is_multiple_of(base)) {
+        return n == base;
+    }
+    let shift = (n - 1).trailing_zeros();
+    let odd = (n - 1) >> shift;
+    'bases: for base in BASES {
+        let mut x = pow_mod(base, odd, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..shift {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'bases;
+            }
+        }
+        return false;
+    }
+    return true;
+}
+
+/// A factor of `n`, odd and not prime, other than 1 and `n`, by Pollard's
+/// rho method.
+fn rho(n: u64) -> u64 {
+    let mut constant = 1;
+    loop {
+        let step = |x: u64| (mul_mod(x, x, n) + constant) % n;
+        let (mut slow, mut fast, mut divisor) = (2, 2, 1);
+        while divisor == 1 {
+            slow = step(slow);
+            fast = step(step(fast));
+            divisor = extended_gcd(slow.abs_diff(fast) as i128, n as i128).0 as u64;
+        }
+        if divisor != n {
+            return divisor;
+        }
+        constant = constant + 1;
+    }
+}
@@ -0,0 +1,119 @@
+//! Steps through a course one command at a time, forwards and backwards, as
+//! a debugger would. The path is recorded up front with `record`, so the
+//! positions are those `part1` and `part2` compute.
+
+use aoc_core::Result;
+
+use crate::{record, Navigator, Step, Trajectory, Waypoint};
+
+/// A course and how far into it the replay is: `at` commands have run and
+/// `steps[at]`, if any, runs next.
+pub struct Replay<'a> {
+    steps: &'a [Step],
+    trajectory: Trajectory,
+    at: usize,
+}
+
+impl<'a> Replay<'a> {
+    /// A replay of `steps` with `navigator`, before the first command.
+    pub fn new<N: Navigator>(navigator: N, steps: &'a [Step]) -> Result<Replay<'a>> {
+        let (_, trajectory) = record(navigator, steps)?;
+        return Ok(Replay { steps: steps, trajectory: trajectory, at: 0 });
+    }
+
+    /// How many commands have run.
+    pub fn at(&self) -> usize {
+        return self.at;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.steps.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.steps.is_empty();
+    }
+
+    /// Where the submarine is now.
+    pub fn waypoint(&self) -> Waypoint {
+        return self.trajectory.waypoints[self.at];
+    }
+
+    /// The command that runs next, `None` at the end of the course.
+    pub fn next_step(&self) -> Option<&'a Step> {
+        return self.steps.get(self.at);
+    }
+
+    /// Runs up to `count` commands, returning how many ran.
+    pub fn forward(&mut self, count: usize) -> usize {
+        let moved = count.min(self.steps.len() - self.at);
+        self.at = self.at + moved;
+        return moved;
+    }
+
+    /// Undoes up to `count` commands, returning how many were undone.
+    pub fn back(&mut self, count: usize) -> usize {
+        let moved = count.min(self.at);
+        self.at = self.at - moved;
+        return moved;
+    }
+
+    /// Goes to just before the first command read from `line`, if any.
+    pub fn to_line(&mut self, line: usize) -> bool {
+        match self.steps.iter().position(|s| s.line == line) {
+            Some(idx) => {
+                self.at = idx;
+                return true;
+            },
+            None => return false,
+        }
+    }
+
+    /// Runs commands until one takes the submarine deeper than `depth` or the
+    /// course ends, returning whether it went deeper.
+    pub fn run_until_deeper(&mut self, depth: i64) -> bool {
+        let mut deeper = self.waypoint().position.y > depth;
+        while self.forward(1) == 1 {
+            let was_deeper = deeper;
+            deeper = self.waypoint().position.y > depth;
+            if deeper && !was_deeper {
+                return true;
+            }
+        }
+        return false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, SimpleNavigator};
+
+    #[test]
+    fn runs_until_the_depth_is_crossed() {
+        let steps = parse("down 5\ndown 5\nforward 2\nup 8\ndown 9").unwrap();
+        let mut replay = Replay::new(SimpleNavigator::default(), &steps).unwrap();
+        assert!(replay.run_until_deeper(8));
+        assert_eq!(replay.at(), 2);
+        assert_eq!(replay.waypoint().position.y, 10);
+        // Staying deeper is not crossing again, but coming back up and going
+        // down is.
+        assert!(replay.run_until_deeper(8));
+        assert_eq!(replay.at(), 5);
+        assert!(!replay.run_until_deeper(8));
+        assert_eq!(replay.at(), 5);
+    }
+
+    #[test]
+    fn already_deeper_at_the_start() {
+        let steps = parse("down 5\nup 8\ndown 4\ndown 2").unwrap();
+        let mut replay = Replay::new(SimpleNavigator::default(), &steps).unwrap();
+        replay.forward(1);
+        assert!(replay.run_until_deeper(2));
+        assert_eq!(replay.at(), 4);
+
+        replay.back(4);
+        assert!(!replay.run_until_deeper(-10));
+        assert_eq!(replay.at(), replay.len());
+    }
+}
@@ -0,0 +1,175 @@
+//! The path the submarine follows along a course, recorded step by step, and
+//! its export as CSV, JSON or an SVG profile.
+
+use std::fmt::Write;
+
+use aoc_core::json::Json;
+use aoc_core::Result;
+
+use crate::{navigate_with, Navigator, Position, Step};
+
+/// Where the submarine is after a step. `step` is the index of the command in
+/// the course, `None` for the starting point.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Waypoint {
+    pub step: Option<usize>,
+    pub line: Option<usize>,
+    pub position: Position,
+    pub aim: i64,
+}
+
+impl Waypoint {
+    pub fn to_json(&self) -> Json {
+        let optional = |value: Option<usize>| value.map_or(Json::Null, |v| Json::from(v as u64));
+        return Json::object(vec![
+            ("step", optional(self.step)),
+            ("line", optional(self.line)),
+            ("x", Json::from(self.position.x)),
+            ("z", Json::from(self.position.z)),
+            ("depth", Json::from(self.position.y)),
+            ("aim", Json::from(self.aim)),
+        ]);
+    }
+}
+
+/// Every waypoint of a course, starting where the navigator started.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trajectory {
+    pub waypoints: Vec<Waypoint>,
+}
+
+/// Follows the course like `navigate`, recording a waypoint after each step.
+pub fn record<N: Navigator>(navigator: N, steps: &[Step]) -> Result<(N, Trajectory)> {
+    let mut waypoints = vec![Waypoint {
+        step: None,
+        line: None,
+        position: navigator.position(),
+        aim: navigator.aim(),
+    }];
+    let navigator = navigate_with(navigator, steps, |idx, step, navigator| {
+        waypoints.push(Waypoint {
+            step: Some(idx),
+            line: Some(step.line),
+            position: navigator.position(),
+            aim: navigator.aim(),
+        });
+    })?;
+    return Ok((navigator, Trajectory { waypoints: waypoints }));
+}
+
+/// The first waypoint at which two trajectories differ in position or aim.
+/// `step` indexes both lists of waypoints; a side is `None` if its course had
+/// already ended.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Divergence {
+    pub step: usize,
+    pub left: Option<Waypoint>,
+    pub right: Option<Waypoint>,
+}
+
+/// Where `left` and `right` part ways, or `None` if they are the same path.
+pub fn diverge(left: &Trajectory, right: &Trajectory) -> Option<Divergence> {
+    let same = |a: &Waypoint, b: &Waypoint| a.position == b.position && a.aim == b.aim;
+    let steps = left.waypoints.len().max(right.waypoints.len());
+    return (0..steps)
+        .map(|idx| Divergence { step: idx, left: left.waypoints.get(idx).copied(), right: right.waypoints.get(idx).copied() })
+        .find(|d| !matches!((&d.left, &d.right), (Some(a), Some(b)) if same(a, b)));
+}
+
+const SVG_MARGIN: f64 = 40.0;
+
+impl Trajectory {
+    /// One row per waypoint under a `step,line,x,z,depth,aim` header; the
+    /// start has empty `step` and `line`.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("step,line,x,z,depth,aim\n");
+        for waypoint in &self.waypoints {
+            let optional = |value: Option<usize>| value.map_or(String::new(), |v| v.to_string());
+            writeln!(
+                csv,
+                "{},{},{},{},{},{}",
+                optional(waypoint.step), optional(waypoint.line),
+                waypoint.position.x, waypoint.position.z, waypoint.position.y, waypoint.aim
+            ).unwrap();
+        }
+        return csv;
+    }
+
+    pub fn to_json(&self) -> Json {
+        return Json::Array(self.waypoints.iter().map(|w| w.to_json()).collect());
+    }
+
+    /// The path as a polyline, horizontal distance to the right and depth
+    /// downwards, scaled to fit `width` by `height` pixels.
+    pub fn to_svg(&self, width: usize, height: usize) -> String {
+        let xs = self.waypoints.iter().map(|w| w.position.x);
+        let ys = self.waypoints.iter().map(|w| w.position.y);
+        let (min_x, max_x) = (xs.clone().min().unwrap_or(0), xs.max().unwrap_or(0));
+        let (min_y, max_y) = (ys.clone().min().unwrap_or(0), ys.max().unwrap_or(0));
+        let scale = |value: i64, min: i64, max: i64, size: usize| match max > min {
+            true => SVG_MARGIN + (value as f64 - min as f64) / (max as f64 - min as f64) * (size as f64 - 2.0 * SVG_MARGIN),
+            false => SVG_MARGIN,
+        };
+
+        let mut points = String::new();
+        for waypoint in &self.waypoints {
+            let x = scale(waypoint.position.x, min_x, max_x, width);
+            let y = scale(waypoint.position.y, min_y, max_y, height);
+            write!(points, "{:.1},{:.1} ", x, y).unwrap();
+        }
+
+        let mut svg = String::new();
+        writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-family="sans-serif" font-size="12">"#, width, height).unwrap();
+        writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#).unwrap();
+        writeln!(svg, r#"<text x="{}" y="{}">x {} to {}, depth {} to {}</text>"#, SVG_MARGIN, SVG_MARGIN / 2.0, min_x, max_x, min_y, max_y).unwrap();
+        writeln!(svg, r##"<polyline points="{}" fill="none" stroke="#1f77b4"/>"##, points.trim_end()).unwrap();
+        svg.push_str("</svg>\n");
+        return svg;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, AimNavigator, SimpleNavigator};
+
+    fn trajectory(course: &str) -> Trajectory {
+        return record(AimNavigator::default(), &parse(course).unwrap()).unwrap().1;
+    }
+
+    #[test]
+    fn same_path_does_not_diverge() {
+        let left = trajectory("forward 5\ndown 5\nforward 8");
+        assert_eq!(diverge(&left, &left), None);
+        // Only position and aim are compared, not the heading.
+        let left = trajectory("forward 5\nturn left\nturn right\nforward 1");
+        let right = trajectory("forward 5\nturn right\nturn left\nforward 1");
+        assert_eq!(diverge(&left, &right), None);
+    }
+
+    #[test]
+    fn finds_the_first_difference() {
+        let left = trajectory("forward 5\ndown 5\nforward 8");
+        let right = trajectory("forward 5\ndown 4\nforward 8");
+        let divergence = diverge(&left, &right).unwrap();
+        assert_eq!(divergence.step, 2);
+        assert_eq!(divergence.left.unwrap().aim, 5);
+        assert_eq!(divergence.right.unwrap().aim, 4);
+
+        // Part 1 dives where part 2 aims, so they part at the first `down`.
+        let steps = parse("forward 5\ndown 5\nforward 8").unwrap();
+        let (_, simple) = record(SimpleNavigator::default(), &steps).unwrap();
+        assert_eq!(diverge(&left, &simple).map(|d| d.step), Some(2));
+    }
+
+    #[test]
+    fn shorter_course_ends_first() {
+        let left = trajectory("forward 5\ndown 5");
+        let right = trajectory("forward 5\ndown 5\nforward 8");
+        let divergence = diverge(&left, &right).unwrap();
+        assert_eq!(divergence.step, 3);
+        assert_eq!(divergence.left, None);
+        assert_eq!(divergence.right.unwrap().position, Position { x: 13, y: 40, z: 0 });
+        assert_eq!(diverge(&right, &left).unwrap().right, None);
+    }
+}
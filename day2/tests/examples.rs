@@ -0,0 +1 @@
+aoc_core::example_tests!(day2::Day2);
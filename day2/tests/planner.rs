@@ -0,0 +1,100 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+
+use day2::{navigate, plan_part1, plan_part2, record, AimNavigator, Command, Limits, Navigator, SimpleNavigator, Step};
+
+fn steps(course: &[Command]) -> Vec<Step> {
+    return course.iter().enumerate().map(|(idx, &command)| Step { line: idx + 1, command: command }).collect();
+}
+
+/// Whether every move of `course` keeps to the maximum step. `up` and
+/// `down` only move the submarine in part 1.
+fn within(course: &[Command], limits: Limits, part: u8) -> bool {
+    return course.iter().all(|command| match command {
+        Command::Forward(amount) => limits.max_step.is_none_or(|step| *amount <= step),
+        Command::Up(amount) | Command::Down(amount) if part == 1 => limits.max_step.is_none_or(|step| *amount <= step),
+        _ => true,
+    });
+}
+
+/// The fewest commands that take the submarine to each `(x, depth)` with
+/// `x <= max_x`, found by trying every course under the rules of part 2.
+fn shortest(max_x: i64, limits: Limits) -> HashMap<(i64, i64), usize> {
+    let max_aim = limits.max_aim.unwrap();
+    let max_step = limits.max_step.unwrap_or(max_x);
+    let mut seen: HashMap<(i64, i64, i64), usize> = HashMap::from([((0, 0, 0), 0)]);
+    let mut queue: VecDeque<(i64, i64, i64)> = VecDeque::from([(0, 0, 0)]);
+    while let Some((x, y, aim)) = queue.pop_front() {
+        let length = seen[&(x, y, aim)];
+        let moves = (1..=max_step.min(max_x - x)).map(|dx| (x + dx, y + dx * aim, aim));
+        let aims = (-max_aim..=max_aim).filter(|&a| a != aim).map(|a| (x, y, a));
+        for next in moves.chain(aims).collect::<Vec<_>>() {
+            if let Entry::Vacant(entry) = seen.entry(next) {
+                entry.insert(length + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    let mut fewest: HashMap<(i64, i64), usize> = HashMap::new();
+    for (&(x, y, _), &length) in &seen {
+        let best = fewest.entry((x, y)).or_insert(length);
+        *best = (*best).min(length);
+    }
+    return fewest;
+}
+
+#[test]
+fn part1_plans_reach_the_target() {
+    for max_step in [None, Some(1), Some(3)] {
+        let limits = Limits { max_aim: None, max_step: max_step };
+        for x in 0..=7 {
+            for depth in -7..=7 {
+                let course = plan_part1(x, depth, limits).unwrap();
+                let position = navigate(SimpleNavigator::default(), &steps(&course)).unwrap().position();
+                assert_eq!((position.x, position.y), (x, depth), "{:?}", course);
+                assert!(within(&course, limits, 1), "{:?}", course);
+            }
+        }
+    }
+}
+
+/// Checks every part 2 plan up to `max_x` against the shortest courses.
+fn check_part2(max_x: i64, max_aims: &[i64], max_steps: &[Option<i64>]) {
+    for &max_aim in max_aims {
+        for &max_step in max_steps {
+            let limits = Limits { max_aim: Some(max_aim), max_step: max_step };
+            let fewest = shortest(max_x, limits);
+            for x in 0..=max_x {
+                let reach = (max_aim + 1).max(4) * max_x;
+                for depth in -reach..=reach {
+                    let plan = plan_part2(x, depth, limits);
+                    let expected = fewest.get(&(x, depth));
+                    assert_eq!(plan.is_ok(), expected.is_some(), "{},{} {:?}: {:?}", x, depth, limits, plan);
+                    let course = match plan {
+                        Ok(course) => course,
+                        Err(_) => continue,
+                    };
+
+                    let (navigator, trajectory) = record(AimNavigator::default(), &steps(&course)).unwrap();
+                    assert_eq!((navigator.position().x, navigator.position().y), (x, depth), "{:?}", course);
+                    assert!(trajectory.waypoints.iter().all(|w| w.aim.abs() <= max_aim), "{:?}", course);
+                    assert!(within(&course, limits, 2), "{:?}", course);
+                    assert_eq!(Some(&course.len()), expected, "{},{} {:?}: {:?}", x, depth, limits, course);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn part2_plans_are_shortest() {
+    check_part2(8, &[0, 1, 2, 3, 5], &[None, Some(1), Some(2), Some(3), Some(5)]);
+}
+
+/// Far enough, with a small maximum aim, that two changes of aim are
+/// searched by aim rather than by place.
+#[test]
+fn far_part2_plans_are_shortest() {
+    check_part2(40, &[1, 2], &[Some(2), Some(3), Some(4), Some(7)]);
+}
@@ -0,0 +1,120 @@
+//! Day 3: Binary Diagnostic. Derives rates from the most and least common
+//! bits of a diagnostic report.
+
+use aoc_core::{info, AocError, Result, Solution};
+
+pub mod report;
+pub mod wide;
+
+pub use report::{Report, Selection};
+pub use wide::Wide;
+
+/// Parses the report lines, checked to be binary numbers of the same width.
+pub fn parse(input: &str) -> Result<Report> {
+    return Report::parse(input);
+}
+
+/// Power consumption: the gamma rate, made of the most common bit of each
+/// column, times the epsilon rate, made of the least common ones.
+pub fn part1(report: &Report) -> Wide {
+    let mut gamma = Wide::default();
+    let mut epsilon = Wide::default();
+    for (idx, column) in report.columns().iter().enumerate() {
+        let ones = column.count();
+        let bit = report.width() - 1 - idx;
+        if ones > report.len() - ones {
+            gamma.set_bit(bit);
+        } else {
+            epsilon.set_bit(bit);
+        }
+    }
+
+    info!("Gamma: {}", gamma);
+    info!("Epsilon: {}", epsilon);
+    return gamma.mul(&epsilon);
+}
+
+enum Gas {
+    Oxygen,
+    CO2
+}
+
+/// Filters the report column by column, keeping the numbers with the bit
+/// the gas asks for, until a single one is left.
+fn find_gas(gas: Gas, report: &Report, columns: &[Selection]) -> Result<Wide> {
+    if report.is_empty() {
+        return Err(AocError::no_solution("the report is empty"));
+    }
+    let mut remaining = Selection::all(report.len());
+    for column in columns {
+        let total = remaining.count();
+        let ones = remaining.count_and(column);
+        let zeros = total - ones;
+        let keep_ones = match gas {
+            Gas::Oxygen => zeros <= ones,
+            Gas::CO2 => zeros > ones,
+        };
+        remaining.filter(column, keep_ones);
+
+        match remaining.count() {
+            0 => return Err(AocError::no_solution("the report is empty")),
+            1 => {
+                let idx = remaining.indices().next().unwrap();
+                return Ok(report.value(idx));
+            },
+            _ => (),
+        }
+    }
+    return Err(AocError::no_solution("the report has repeated values"));
+}
+
+/// Life support rating: the oxygen generator rating times the CO2 scrubber
+/// rating, each found by filtering the report bit by bit.
+pub fn part2(report: &Report) -> Result<Wide> {
+    let columns = report.columns();
+    let oxygen = find_gas(Gas::Oxygen, report, &columns)?;
+    let co2 = find_gas(Gas::CO2, report, &columns)?;
+    info!("O: {}", oxygen);
+    info!("CO2: {}", co2);
+
+    return Ok(oxygen.mul(&co2));
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    fn day(&self) -> u8 {
+        return 3;
+    }
+
+    fn default_input(&self) -> &'static str {
+        return concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+    }
+
+    fn part1(&self, input: &str) -> Result<String> {
+        return parse(input).map(|report| part1(&report).to_string());
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        return parse(input).and_then(|report| part2(&report)).map(|answer| answer.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_wider_than_64_bits() {
+        // The example with every number repeated 14 times, so the rates are
+        // those of the example repeated too.
+        let example = "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010";
+        let wide: Vec<String> = example.lines().map(|line| line.repeat(14)).collect();
+        let report = parse(&wide.join("\n")).unwrap();
+        assert_eq!(report.width(), 70);
+        // 10110... times 01001...
+        assert_eq!(part1(&report).to_string(), "287171406692837108611901143956523807242822");
+        // 10111... times 01010...
+        assert_eq!(part2(&report).unwrap().to_string(), "333582947168447146367359914696972099322470");
+    }
+}
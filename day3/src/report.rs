@@ -0,0 +1,202 @@
+//! The diagnostic report as integers: every line is parsed once into a
+//! `u64`, or several for reports wider than 64 bits, and columns are counted
+//! a word of 64 numbers at a time.
+
+use aoc_core::{AocError, Result};
+
+use crate::Wide;
+
+/// A bit set with a bit for each number of a report, such as the numbers
+/// with a 1 in some column or those still kept by a filter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selection {
+    words: Vec<u64>,
+}
+
+impl Selection {
+    /// Every one of `len` numbers.
+    pub fn all(len: usize) -> Selection {
+        let mut words = vec![!0u64; len.div_ceil(64)];
+        if !len.is_multiple_of(64) {
+            *words.last_mut().unwrap() = (1 << (len % 64)) - 1;
+        }
+        return Selection { words: words };
+    }
+
+    fn none(len: usize) -> Selection {
+        return Selection { words: vec![0; len.div_ceil(64)] };
+    }
+
+    fn insert(&mut self, idx: usize) {
+        self.words[idx / 64] = self.words[idx / 64] | 1 << (idx % 64);
+    }
+
+    pub fn count(&self) -> usize {
+        return self.words.iter().map(|w| w.count_ones() as usize).sum();
+    }
+
+    /// How many numbers are in both selections.
+    pub fn count_and(&self, other: &Selection) -> usize {
+        return self.words.iter().zip(&other.words).map(|(a, b)| (a & b).count_ones() as usize).sum();
+    }
+
+    /// Keeps the numbers that are also in `other`, or with `keep` false, the
+    /// ones that are not.
+    pub fn filter(&mut self, other: &Selection, keep: bool) {
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word = if keep { *word & other } else { *word & !other };
+        }
+    }
+
+    /// The numbers selected, in order.
+    pub fn indices(&self) -> impl Iterator<Item = usize> + '_ {
+        return self.words.iter().enumerate().flat_map(|(idx, &word)| {
+            (0..64).filter(move |bit| word & 1 << bit != 0).map(move |bit| idx * 64 + bit)
+        });
+    }
+}
+
+/// The numbers of a diagnostic report, all `width` bits wide. Each takes
+/// `ceil(width / 64)` words, least significant first, so a report up to 64
+/// bits wide holds one `u64` per number.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    width: usize,
+    words: usize,
+    values: Vec<u64>,
+}
+
+impl Report {
+    /// Parses the report lines, checked to be binary numbers of the same width.
+    pub fn parse(input: &str) -> Result<Report> {
+        let mut report = Report { width: 0, words: 0, values: Vec::new() };
+        for (idx, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.bytes().any(|c| c != b'0' && c != b'1') {
+                return Err(AocError::parse(idx + 1, line, "expected a binary number"));
+            }
+            if idx == 0 {
+                report.width = line.len();
+                report.words = line.len().div_ceil(64);
+            } else if line.len() != report.width {
+                return Err(AocError::parse(idx + 1, line, "binary numbers must all have the same width"));
+            }
+            // The last 64 digits make the first word, and so on.
+            for digits in line.as_bytes().rchunks(64) {
+                report.values.push(digits.iter().fold(0, |word, c| word << 1 | (c - b'0') as u64));
+            }
+        }
+        return Ok(report);
+    }
+
+    /// How many bits every number has.
+    pub fn width(&self) -> usize {
+        return self.width;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.values.len().checked_div(self.words).unwrap_or(0);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.values.is_empty();
+    }
+
+    /// The words of number `idx`, least significant first.
+    pub fn words(&self, idx: usize) -> &[u64] {
+        return &self.values[idx * self.words..(idx + 1) * self.words];
+    }
+
+    pub fn value(&self, idx: usize) -> Wide {
+        return Wide::from_words(self.words(idx));
+    }
+
+    /// For each column, from the left, the numbers with a 1 in it.
+    pub fn columns(&self) -> Vec<Selection> {
+        let mut columns = vec![Selection::none(self.len()); self.width];
+        for idx in 0..self.len() {
+            for (word_idx, &word) in self.words(idx).iter().enumerate() {
+                // Visits only the bits that are set, lowest first.
+                let mut word = word;
+                while word != 0 {
+                    let bit = word_idx * 64 + word.trailing_zeros() as usize;
+                    columns[self.width - 1 - bit].insert(idx);
+                    word = word & (word - 1);
+                }
+            }
+        }
+        return columns;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn indices(selection: &Selection) -> Vec<usize> {
+        return selection.indices().collect();
+    }
+
+    #[test]
+    fn all_fills_only_the_numbers_there_are() {
+        for len in [0, 1, 63, 64, 65, 128, 130] {
+            let all = Selection::all(len);
+            assert_eq!(all.count(), len);
+            assert_eq!(indices(&all), (0..len).collect::<Vec<usize>>());
+        }
+    }
+
+    #[test]
+    fn filter_across_words() {
+        let mut odd = Selection::none(130);
+        for idx in (1..130).step_by(2) {
+            odd.insert(idx);
+        }
+        let mut around = Selection::none(130);
+        for idx in [0, 63, 64, 65, 127, 128, 129] {
+            around.insert(idx);
+        }
+        assert_eq!(odd.count_and(&around), 4);
+
+        let mut kept = Selection::all(130);
+        kept.filter(&around, true);
+        assert_eq!(indices(&kept), vec![0, 63, 64, 65, 127, 128, 129]);
+        kept.filter(&odd, false);
+        assert_eq!(indices(&kept), vec![0, 64, 128]);
+        kept.filter(&odd, true);
+        assert_eq!(kept.count(), 0);
+    }
+
+    #[test]
+    fn columns_count_from_the_left() {
+        let report = Report::parse("100\n110\n011\n").unwrap();
+        let columns: Vec<Vec<usize>> = report.columns().iter().map(indices).collect();
+        assert_eq!(columns, vec![vec![0, 1], vec![1, 2], vec![2]]);
+    }
+
+    #[test]
+    fn columns_of_many_wide_numbers() {
+        let lines: Vec<String> = (0..100u64).map(|n| format!("{:064b}", n.wrapping_mul(0x9e37_79b9_7f4a_7c15))).collect();
+        let report = Report::parse(&lines.join("\n")).unwrap();
+        assert_eq!(report.width(), 64);
+        for (column, selection) in report.columns().iter().enumerate() {
+            let expected: Vec<usize> = (0..100).filter(|&idx| lines[idx].as_bytes()[column] == b'1').collect();
+            assert_eq!(indices(selection), expected);
+        }
+    }
+
+    #[test]
+    fn numbers_over_64_bits_take_several_words() {
+        let lines = ["1".repeat(130), format!("1{}", "0".repeat(129)), format!("{}1{}", "0".repeat(65), "0".repeat(64))];
+        let report = Report::parse(&lines.join("\n")).unwrap();
+        assert_eq!((report.width(), report.len()), (130, 3));
+        assert_eq!(report.words(0), &[!0, !0, 3]);
+        assert_eq!(report.words(1), &[0, 0, 2]);
+        assert_eq!(report.words(2), &[0, 1, 0]);
+        assert_eq!(report.value(2), Wide::from(1u128 << 64));
+        let columns: Vec<Vec<usize>> = report.columns().iter().map(indices).collect();
+        assert_eq!(columns[0], vec![0, 1]);
+        assert_eq!(columns[65], vec![0, 2]);
+        assert_eq!(columns[129], vec![0]);
+    }
+}
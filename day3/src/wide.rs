@@ -0,0 +1,111 @@
+//! Unsigned integers of any width, for the numbers of reports wider than 64
+//! bits and the rates made from them.
+
+use std::fmt;
+
+/// An unsigned integer stored as 64-bit words, least significant first, with
+/// no zero words at the top.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Wide {
+    words: Vec<u64>,
+}
+
+/// The largest power of ten that fits a `u64`, so the decimal digits come out
+/// 19 at a time.
+const DECIMAL_CHUNK: u128 = 10_000_000_000_000_000_000;
+
+impl Wide {
+    /// The integer whose words, least significant first, are `words`.
+    pub fn from_words(words: &[u64]) -> Wide {
+        let len = words.iter().rposition(|&w| w != 0).map_or(0, |idx| idx + 1);
+        return Wide { words: words[..len].to_vec() };
+    }
+
+    /// Sets bit `bit`, counted from the least significant.
+    pub fn set_bit(&mut self, bit: usize) {
+        if self.words.len() <= bit / 64 {
+            self.words.resize(bit / 64 + 1, 0);
+        }
+        self.words[bit / 64] = self.words[bit / 64] | 1 << (bit % 64);
+    }
+
+    /// The product, by long multiplication a word at a time.
+    pub fn mul(&self, other: &Wide) -> Wide {
+        let mut product = vec![0u64; self.words.len() + other.words.len()];
+        for (i, &a) in self.words.iter().enumerate() {
+            let mut carry: u128 = 0;
+            for (j, &b) in other.words.iter().enumerate() {
+                let sum = a as u128 * b as u128 + product[i + j] as u128 + carry;
+                product[i + j] = sum as u64;
+                carry = sum >> 64;
+            }
+            product[i + other.words.len()] = carry as u64;
+        }
+        return Wide::from_words(&product);
+    }
+}
+
+impl From<u128> for Wide {
+    fn from(value: u128) -> Wide {
+        return Wide::from_words(&[value as u64, (value >> 64) as u64]);
+    }
+}
+
+impl fmt::Display for Wide {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Divides by 10^19 until nothing is left, collecting the remainders.
+        let mut words = self.words.clone();
+        let mut chunks: Vec<u64> = Vec::new();
+        while !words.is_empty() {
+            let mut remainder: u128 = 0;
+            for word in words.iter_mut().rev() {
+                let value = remainder << 64 | *word as u128;
+                *word = (value / DECIMAL_CHUNK) as u64;
+                remainder = value % DECIMAL_CHUNK;
+            }
+            chunks.push(remainder as u64);
+            while words.last() == Some(&0) {
+                words.pop();
+            }
+        }
+
+        match chunks.pop() {
+            Some(top) => write!(f, "{}", top)?,
+            None => write!(f, "0")?,
+        }
+        for chunk in chunks.iter().rev() {
+            write!(f, "{:019}", chunk)?;
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prints_in_decimal() {
+        for value in [0, 7, 10_000_000_000_000_000_000, u64::MAX as u128 + 1, u128::MAX] {
+            assert_eq!(Wide::from(value).to_string(), value.to_string());
+        }
+        assert_eq!(Wide::from_words(&[0, 0, 1]).to_string(), "340282366920938463463374607431768211456");
+    }
+
+    #[test]
+    fn multiplies_across_words() {
+        for (a, b) in [(0, 5), (3, 5), (u64::MAX as u128, u64::MAX as u128), (1 << 70, 12_345)] {
+            assert_eq!(Wide::from(a).mul(&Wide::from(b)), Wide::from(a * b));
+        }
+        let max = Wide::from(u128::MAX);
+        assert_eq!(max.mul(&max).to_string(), "115792089237316195423570985008687907852589419931798687112530834793049593217025");
+    }
+
+    #[test]
+    fn sets_bits_past_the_top() {
+        let mut wide = Wide::default();
+        wide.set_bit(0);
+        wide.set_bit(130);
+        assert_eq!(wide, Wide::from_words(&[1, 0, 4, 0]));
+    }
+}
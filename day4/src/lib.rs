@@ -0,0 +1,193 @@
+//! Day 4: Giant Squid. Plays bingo to find the first and the last winning
+//! boards.
+
+use aoc_core::{debug, first_line, info, parse_comma_separated, parse_value, trace, AocError, Grid, Result, Solution};
+
+/// A bingo board and the values marked on it so far.
+#[derive(Clone)]
+pub struct BingoBoard {
+    values: Grid<u16>,
+    marked: Grid<bool>,
+}
+
+impl BingoBoard {
+    /// Builds an unmarked board from its values, row by row.
+    pub fn from_values(values: Vec<u16>, n_rows: u16) -> BingoBoard{
+        let n_cols = values.len() / n_rows as usize;
+        let values = Grid::from_cells(values, n_cols);
+
+        return BingoBoard {
+            marked: Grid::filled(values.width(), values.height(), false),
+            values: values,
+        };
+    }
+
+    /// Marks `value` if it is on the board.
+    pub fn mark_value(&mut self, value: u16) {
+        if let Some((x, y)) = self.values.position(&value) {
+            self.marked.set(x, y, true);
+        }
+    }
+    
+    /// Whether a full row or column is marked.
+    pub fn is_winner(&self) -> bool{
+        let width = self.marked.width();
+        let height = self.marked.height();
+
+        let any_row = (0..height).any(|y| self.marked.row(y).iter().all(|m| *m));
+        let any_column = (0..width).any(|x| (0..height).all(|y| *self.marked.get(x, y)));
+        return any_row || any_column;
+    }
+
+    /// The sum of the unmarked values times the last drawn value.
+    pub fn score(&self, winner_value: u64) -> u64 {
+        let mut sum: u64 = 0;
+        for (value, is_marked) in self.values.iter().zip(self.marked.iter()) {
+            if !is_marked{
+                sum = sum + *value as u64;
+            }
+        }
+        return sum * winner_value;
+    }
+
+}
+
+/// The board a row per line, marked values prefixed by `+` and the rest by `-`.
+impl std::fmt::Display for BingoBoard {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for y in 0..self.values.height() {
+            for x in 0..self.values.width() {
+                let prefix = match self.marked.get(x, y) {
+                    true => "+",
+                    false => "-",
+                };
+                write!(f, "{}{} ", prefix, self.values.get(x, y))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+
+/// Loads the boards from numbered lines, `(line_number, line)`.
+fn load_boards<'a, C>(input: C) -> Result<Vec<BingoBoard>>
+        where C: IntoIterator<Item = (usize, &'a str)> {
+    let mut boards: Vec<BingoBoard> = Vec::new();
+    let mut current_values: Vec<u16> = Vec::new();
+    let mut rows_count = 0;
+
+    for (line_number, line) in input {
+        if line.is_empty() {
+            if rows_count == 0{
+                continue
+            }
+            let board = BingoBoard::from_values(current_values, rows_count);
+            boards.push(board);
+            current_values = Vec::new();
+            rows_count = 0;
+        } else {
+            rows_count = rows_count + 1;
+            let row_start = current_values.len();
+            let row_values = line.split(" ").collect::<Vec<&str>>();
+            for value in row_values {
+                if value.is_empty() {
+                    continue
+                }
+                current_values.push(parse_value(value, line_number)?);
+            }
+            let row_width = current_values.len() - row_start;
+            if row_width * rows_count as usize != current_values.len() {
+                return Err(AocError::parse(line_number, line, "board rows must all have the same length"));
+            }
+        }
+    }
+    // The last board may not be followed by an empty line
+    if rows_count > 0 {
+        boards.push(BingoBoard::from_values(current_values, rows_count));
+    }
+
+    return Ok(boards);
+}
+
+/// A bingo game: the numbers to draw, in order, and the boards.
+pub struct Bingo {
+    pub draws: Vec<u16>,
+    pub boards: Vec<BingoBoard>,
+}
+
+/// Parses the numbers to draw, from the first line, and the boards.
+pub fn parse(input: &str) -> Result<Bingo> {
+    let draws: Vec<u16> = parse_comma_separated(first_line(input)?, 1)?;
+    let boards = load_boards(input.lines().enumerate().skip(1).map(|(idx, l)| (idx + 1, l)))?;
+    return Ok(Bingo { draws: draws, boards: boards });
+}
+
+
+/// Score of the first board to win.
+pub fn part1(game: &Bingo) -> Result<u64> {
+    let mut boards = game.boards.clone();
+
+    for &winner in game.draws.iter() {
+        trace!("Marking value {}", winner);
+        for board in boards.iter_mut() {
+            board.mark_value(winner);
+
+            if board.is_winner(){
+                debug!("First board is\n{}", board);
+                let score = board.score(winner as u64);
+                info!("Score was {}", score);
+                return Ok(score);
+            }
+        }
+    }
+
+    return Err(AocError::no_solution("no board wins"));
+}
+
+
+/// Score of the last board to win.
+pub fn part2(game: &Bingo) -> Result<u64> {
+    let mut boards = game.boards.clone();
+    let mut last_board: Option<BingoBoard> = None;
+    let mut last_winner = 0;
+
+    for &winner in game.draws.iter() {
+        boards.retain(|b| !b.is_winner());
+        if boards.is_empty() {
+            break;
+        }
+
+        trace!("Marking value {}", winner);
+        for board in boards.iter_mut() {
+            board.mark_value(winner);
+        }
+        last_board = Some(boards[0].clone());
+        last_winner = winner;
+    }
+
+    let last_board = last_board.ok_or(AocError::no_solution("no numbers are drawn"))?;
+    debug!("Last board was\n{}", last_board);
+
+    return Ok(last_board.score(last_winner as u64));
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    fn day(&self) -> u8 {
+        return 4;
+    }
+
+    fn default_input(&self) -> &'static str {
+        return concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+    }
+
+    fn part1(&self, input: &str) -> Result<String> {
+        return parse(input).and_then(|game| part1(&game)).map(|answer| answer.to_string());
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        return parse(input).and_then(|game| part2(&game)).map(|answer| answer.to_string());
+    }
+}
@@ -0,0 +1,195 @@
+//! Day 5: Hydrothermal Venture. Counts the points where the lines of vents
+//! overlap.
+
+use std::collections::HashMap;
+use std::cmp;
+
+use aoc_core::log::{self, Level};
+use aoc_core::{parse_value, trace, AocError, Point, Result, Solution};
+
+/// The largest coordinate, either way. Lines between points within it are
+/// listed point by point without overflowing an `i32`.
+pub const MAX_COORDINATE: i32 = 1_000_000;
+
+fn parse_coordinate(string: &str, line_number: usize) -> Result<i32> {
+    let value: i32 = parse_value(string, line_number)?;
+    if !(-MAX_COORDINATE..=MAX_COORDINATE).contains(&value) {
+        let reason = format!("coordinate out of range, expected -{} to {}", MAX_COORDINATE, MAX_COORDINATE);
+        return Err(AocError::parse(line_number, string.trim(), &reason));
+    }
+    return Ok(value);
+}
+
+fn parse_point(string: &str, line_number: usize) -> Result<Point> {
+    let mut iter = string.split(",");
+    let (x, y) = match (iter.next(), iter.next(), iter.next()) {
+        (Some(x), Some(y), None) => (x, y),
+        _ => return Err(AocError::parse(line_number, string.trim(), "expected a point `x,y`")),
+    };
+    let x = parse_coordinate(x, line_number)?;
+    let y = parse_coordinate(y, line_number)?;
+    return Ok(Point {x: x, y: y});
+}
+
+/// A line of vents between two points, both included.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct VentLine {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl VentLine {
+    /// Parses a `x1,y1 -> x2,y2` line.
+    pub fn from_string(string: &str, line_number: usize) -> Result<VentLine> {
+        let mut split_iter = string.split("->");
+        let (start, end) = match (split_iter.next(), split_iter.next(), split_iter.next()) {
+            (Some(start), Some(end), None) => (start, end),
+            _ => return Err(AocError::parse(line_number, string, "expected `x1,y1 -> x2,y2`")),
+        };
+        let start = parse_point(start, line_number)?;
+        let end = parse_point(end, line_number)?;
+        return Ok(VentLine { start: start, end: end });
+    }
+
+    /// The vector from the start to the end of the line.
+    pub fn direction(&self) -> Point {
+        return self.end - self.start;
+    }
+
+    /// Whether the line is horizontal or vertical.
+    pub fn is_aligned_with_axis(&self) -> bool {
+        let direction = self.direction();
+        return direction.x == 0 || direction.y == 0;
+    }
+
+    /// The points covered by a horizontal, vertical or 45º line. Any other
+    /// line covers none.
+    pub fn list_points(&self) -> Vec<Point> {
+        let mut points: Vec<Point> = Vec::new();
+
+        let vec_dir = self.direction();
+        match vec_dir {
+            Point { x: 0, y: _ } => {
+                // Vertical line
+                let start = cmp::min(self.start.y, self.end.y);
+                let end = cmp::max(self.start.y, self.end.y);
+                for new_y in start..end+1 {
+                    points.push(Point { x: self.start.x, y: new_y });
+                }
+            },
+            Point { x: _, y: 0 } => {
+                // Horizontal line
+                let start = cmp::min(self.start.x, self.end.x);
+                let end = cmp::max(self.start.x, self.end.x);
+                for new_x in start..end+1 {
+                    points.push(Point { x: new_x, y: self.start.y });
+                }
+            },
+            Point { x, y } if x.abs() == y.abs() => {
+                // 45º line
+                let x_sign = x / x.abs();
+                let y_sign = y / y.abs();
+                for inc in 0..x.abs() + 1 {
+                    let offset = Point{ x: inc * x_sign, y: inc * y_sign };
+
+                    points.push(self.start + offset);
+                }
+            },
+            _ => {},
+        };
+
+        return points;
+    }
+}
+
+/// Parses one vent line per input line.
+pub fn parse(input: &str) -> Result<Vec<VentLine>> {
+    let mut vents: Vec<VentLine> = Vec::new();
+    for (idx, line) in input.lines().enumerate() {
+        vents.push(VentLine::from_string(line, idx + 1)?);
+    }
+    return Ok(vents);
+}
+
+fn generate_overlap_map(vents: &Vec<&VentLine>) -> HashMap<Point, u16>{
+    let mut overlap_count: HashMap<Point, u16> = HashMap::new();
+
+    for vent in vents {
+        let points = vent.list_points();
+        for point in points{
+            let current = overlap_count.get(&point);
+            let new_count = match current {
+                Some(count) => count.saturating_add(1),
+                None => 1,
+            };
+            overlap_count.insert(point, new_count);
+        }
+    }
+
+    return overlap_count;
+}
+
+fn render_overlap_map(overlap_map: &HashMap<Point, u16>) -> String {
+    let mut rendered = String::new();
+    let mut max_x = 0;
+    let mut max_y = 0;
+
+    for point in overlap_map.keys(){
+        max_x = cmp::max(point.x, max_x);
+        max_y = cmp::max(point.y, max_y);
+    }
+
+    for y in 0..max_y+1 {
+        for x in 0..max_x+1 {
+            let coord = Point { x: x, y: y };
+            let count = match overlap_map.get(&coord) {
+                Some(count) => count.to_string(),
+                None => "-".to_string(),
+            };
+            rendered.push_str(&format!(" {} ", count));
+        }
+        rendered.push('\n');
+    }
+    return rendered;
+}
+
+fn count_overlaps(vents: &Vec<&VentLine>) -> usize {
+    let overlap_map = generate_overlap_map(vents);
+    if log::enabled(Level::Trace) {
+        trace!("{}", render_overlap_map(&overlap_map));
+    }
+
+    return overlap_map.values().filter(|&x| x >= &2).count();
+}
+
+/// Points where at least two horizontal or vertical lines overlap.
+pub fn part1(vents: &[VentLine]) -> usize {
+    let aligned_vents = vents.iter().filter(|&v| v.is_aligned_with_axis()).collect();
+    return count_overlaps(&aligned_vents);
+}
+
+/// Points where at least two lines overlap, diagonals included.
+pub fn part2(vents: &[VentLine]) -> usize {
+    let all_vents = vents.iter().collect();
+    return count_overlaps(&all_vents);
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    fn day(&self) -> u8 {
+        return 5;
+    }
+
+    fn default_input(&self) -> &'static str {
+        return concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+    }
+
+    fn part1(&self, input: &str) -> Result<String> {
+        return parse(input).map(|vents| part1(&vents).to_string());
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        return parse(input).map(|vents| part2(&vents).to_string());
+    }
+}
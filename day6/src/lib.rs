@@ -0,0 +1,126 @@
+//! Day 6: Lanternfish. Simulates a population of lanternfish grouped by the
+//! days left until they spawn.
+
+use std::collections::HashMap;
+
+use aoc_core::{first_line, info, parse_comma_separated, trace, Result, Solution};
+
+/// Number of fish per days left to spawn.
+#[derive(Clone)]
+pub struct Population {
+    population: HashMap<u8, u64>,
+    current_time: u64,
+}
+
+fn sum_to_hashmap(hashmap: &mut HashMap<u8, u64>, key: u8, to_sum: u64) {
+    let new_count = match hashmap.get(&key) {
+        Some(x) => x + to_sum,
+        None => to_sum,
+    };
+    hashmap.insert(key, new_count);
+}
+
+impl Population {
+    /// Groups the fish by their timers.
+    pub fn new(initial_state: Vec<u8>) -> Population {
+        let mut population: HashMap<u8, u64> = HashMap::new();
+        for fish in initial_state {
+            let new_count = match population.get(&fish) {
+                Some(x) => x + 1,
+                None => 1,
+            };
+            population.insert(fish, new_count);            
+        }
+
+        return Population { population: population, current_time: 0 };
+    }
+
+    /// Advances the simulation one day.
+    pub fn tick(&mut self) {
+        self.current_time = self.current_time + 1;
+
+        let mut new_population: HashMap<u8, u64> = HashMap::new();
+        for (days, count) in self.population.iter() {
+            if days == &0 {
+                // Spawn
+                sum_to_hashmap(&mut new_population, 8, *count);
+                // Reset counter
+                sum_to_hashmap(&mut new_population, 6, *count);
+            } else {
+                sum_to_hashmap(&mut new_population, days-1, *count);
+            }
+        }
+        self.population = new_population;
+    }
+
+    /// The number of fish.
+    pub fn count(&self) -> u64 {
+        return self.population.values().sum();
+    }
+}
+
+impl std::fmt::Display for Population {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "After {} days -> ({}): ", self.current_time, self.count())?;
+        for (days, count) in self.population.iter() {
+            write!(f, "{}x{},", days, count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses the initial timers, comma separated on the first line.
+pub fn parse(input: &str) -> Result<Population> {
+    let initial_state: Vec<u8> = parse_comma_separated(first_line(input)?, 1)?;
+
+    return Ok(Population::new(initial_state));
+}
+
+/// Advances the population `max_day` days.
+pub fn simulate(population: &mut Population, max_day: u32) {
+    trace!("{}", population);
+
+    for i in 0..max_day {
+        trace!("Analyzing day: {}", i);
+        population.tick();
+        trace!("{}", population);
+    }
+
+    info!("After {} days we have {} fish", max_day, population.count());
+}
+
+/// Fish after 80 days.
+pub fn part1(initial: &Population) -> u64 {
+    let max_day = 80;
+    let mut population = initial.clone();
+    simulate(&mut population, max_day);
+    return population.count();
+}
+
+/// Fish after 256 days.
+pub fn part2(initial: &Population) -> u64 {
+    let max_day = 256;
+    let mut population = initial.clone();
+    simulate(&mut population, max_day);
+    return population.count();
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    fn day(&self) -> u8 {
+        return 6;
+    }
+
+    fn default_input(&self) -> &'static str {
+        return concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+    }
+
+    fn part1(&self, input: &str) -> Result<String> {
+        return parse(input).map(|population| part1(&population).to_string());
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        return parse(input).map(|population| part2(&population).to_string());
+    }
+}
@@ -0,0 +1,105 @@
+//! Day 7: The Treachery of Whales. Finds the position where aligning the
+//! crabs costs the least fuel.
+
+use std::cmp::max;
+
+use aoc_core::{first_line, info, parse_comma_separated, AocError, Result, Solution};
+
+fn median(numbers: &[i64]) -> i64 {
+	let mut vals = numbers.to_vec();
+	vals.sort();
+	let mid = vals.len() / 2;
+	return vals[mid];
+}
+
+/// The furthest a crab can be from position 0. Part 2 tries every position up
+/// to the furthest crab, and the costs then fit in an `i64`.
+pub const MAX_POSITION: i64 = 100_000;
+
+/// Parses the crab positions, from the first line.
+pub fn parse(input: &str) -> Result<Vec<i64>> {
+	let values: Vec<i64> = parse_comma_separated(first_line(input)?, 1)?;
+	if let Some(value) = values.iter().find(|&&value| !(0..=MAX_POSITION).contains(&value)) {
+		let reason = format!("position out of range, expected 0 to {}", MAX_POSITION);
+		return Err(AocError::parse(1, &value.to_string(), &reason));
+	}
+	return Ok(values);
+}
+
+/// Fuel to align the crabs when each step costs 1.
+pub fn part1(values: &[i64]) -> i64 {
+	let best_point = median(values);
+	let mut fuel = 0;
+	for value in values {
+		fuel = fuel + (value - best_point).abs();
+	}
+	info!("Fuel used is {}", fuel);
+	return fuel;
+}
+
+fn part2_cost_function(optimal: i64, positions: &[i64]) -> i64 {
+	let mut cost = 0;
+
+	for position in positions{
+		let diff = (position - optimal).abs();
+		let crab_cost = (diff * (diff + 1)) / 2;
+		cost = cost + crab_cost;
+	}
+
+	return cost;
+}
+
+/// Fuel to align the crabs when each step costs one more than the previous.
+pub fn part2(values: &[i64]) -> i64 {
+	let mut max_value = 0;
+	for value in values {
+		max_value = max(*value, max_value);
+	}
+
+	// Brute force the costs
+	let mut best_cost = i64::pow(2, 60);
+
+	for position in 0..max_value+1 {
+		let cost = part2_cost_function(position, values);
+		if best_cost > cost {
+			best_cost = cost;
+		}
+	}
+
+	info!("Best cost is {}", best_cost);
+	return best_cost;
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+	fn day(&self) -> u8 {
+		return 7;
+	}
+
+	fn default_input(&self) -> &'static str {
+		return concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+	}
+
+	fn part1(&self, input: &str) -> Result<String> {
+		return parse(input).map(|values| part1(&values).to_string());
+	}
+
+	fn part2(&self, input: &str) -> Result<String> {
+		return parse(input).map(|values| part2(&values).to_string());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn positions_are_bounded() {
+		assert_eq!(parse("0,100000\n").unwrap(), vec![0, MAX_POSITION]);
+		let error = parse("0,-4000000000").unwrap_err();
+		assert_eq!(error.to_string(), "1: position out of range, expected 0 to 100000: \"-4000000000\"");
+		assert!(parse(&format!("{},-1", i64::MAX)).is_err());
+		assert!(parse("3,100001").is_err());
+	}
+}
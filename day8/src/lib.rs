@@ -0,0 +1,257 @@
+//! Day 8: Seven Segment Search. Works out the wiring of scrambled seven
+//! segment displays and decodes their outputs.
+
+use std::collections::{HashSet, HashMap};
+
+use aoc_core::{AocError, Result, Solution};
+
+/// The digit lit up by each pattern of a display, and the other way around.
+pub struct Mapping {
+    mapping: HashMap<String, u8>,
+    inverse_mapping: HashMap<u8, String>,
+}
+
+impl Mapping {
+    fn new() -> Self {
+        return Mapping {
+            mapping: HashMap::new(),
+            inverse_mapping: HashMap::new()
+        };
+    }
+
+    fn get_key(&self, pattern: &str) -> String{
+        let mut chars: Vec<char> = pattern.chars().collect();
+        chars.sort();
+        return chars.into_iter().collect();
+    }
+
+    fn add_mapping(&mut self, pattern: &str, value: u8) {
+        let pattern = self.get_key(pattern);
+        self.mapping.insert(pattern.to_string(), value);
+        self.inverse_mapping.insert(value, pattern.to_string());
+    }
+
+    /// The digit lit up by `pattern`, in any order of its segments.
+    pub fn identify_pattern(&self, pattern: &str) -> Option<&u8> {
+        let pattern = self.get_key(pattern);
+        return self.mapping.get(&pattern);
+    }
+
+    fn get_pattern(&self, value: &u8) -> Option<HashSet<char>> {
+        return self.inverse_mapping.get(value).map(|x| get_set_of_chars(x));
+    }
+}
+
+
+fn get_set_of_chars(pattern: &str) -> HashSet<char> {
+    let chars: Vec<char> = pattern.chars().collect();
+    return HashSet::from_iter(chars.iter().cloned());
+}
+
+/// Works out which pattern lights up each digit, or `None` if the signals are
+/// not the ten digits of a scrambled display.
+pub fn identify_numbers(signals: &[String]) -> Option<Mapping> {
+    let mut mapping = Mapping::new();
+
+    // Detect with unique length
+    for signal in signals {
+        let signal = &signal.to_string();
+        match signal.len() {
+            2 => {
+                mapping.add_mapping(signal, 1);
+            },
+            4 => {
+                mapping.add_mapping(signal, 4);
+            },
+            3 => {
+                mapping.add_mapping(signal, 7);
+            },
+            7 => {
+                mapping.add_mapping(signal, 8);
+            },
+            _ => (),
+        };
+    }
+
+    // Detect 9
+    // The only one with 6 segments that uses all the signals used by 4
+    let signals_at_4 = mapping.get_pattern(&4)?;
+    for signal in signals {
+        let signal = &signal.to_string();
+        if signal.len() == 6 {
+            let signal_set = get_set_of_chars(signal);
+            let diff = signals_at_4.difference(&signal_set);
+
+            if diff.count() == 0 {
+                mapping.add_mapping(signal, 9);
+                break;
+            }
+        }
+    }
+
+    // Detect 0
+    // Discarding the 9, it is the only one with 6 segments that uses
+    // all the signals used by 1
+    let signals_at_1 = mapping.get_pattern(&1)?;
+    for signal in signals {
+        let signal = &signal.to_string();
+        if signal.len() == 6 && mapping.identify_pattern(signal).is_none() {
+            let signal_set = get_set_of_chars(signal);
+            let diff = signals_at_1.difference(&signal_set);
+
+            if diff.count() == 0 {
+                mapping.add_mapping(signal, 0);
+                break;
+            }
+        }
+    }
+
+    // Detect 6
+    // Discarding 0 and 9, is the only one that uses 6 segments
+    for signal in signals {
+        let signal = &signal.to_string();
+        if signal.len() == 6 && mapping.identify_pattern(signal).is_none() {
+            mapping.add_mapping(signal, 6);
+            break;
+        }
+    }
+
+    // Detect 3
+    // Discarding the others found, signals for 1 are only a subset
+    // of signals at 3
+    for signal in signals {
+        let signal = &signal.to_string();
+        if mapping.identify_pattern(signal).is_none() {
+            let signal_set = get_set_of_chars(signal);
+            if signals_at_1.is_subset(&signal_set) {
+                mapping.add_mapping(signal, 3);
+                break;
+            }
+        }
+    }
+
+    // Detect 5
+    // union between 5 and 1 = 9
+    let signals_at_9 = mapping.get_pattern(&9)?;
+    for signal in signals {
+        let signal = &signal.to_string();
+
+        if mapping.identify_pattern(signal).is_none() {
+            let signal_set = get_set_of_chars(signal);
+            if signal_set.is_subset(&signals_at_9) {
+                mapping.add_mapping(signal, 5);
+                break;
+            }
+        }
+    }
+
+    // Detect 2
+    // the last one
+    for signal in signals {
+        let signal = &signal.to_string();
+        if mapping.identify_pattern(signal).is_none() {
+            mapping.add_mapping(signal, 2);
+            break;
+        }
+    }
+
+
+    return Some(mapping);
+}
+
+/// A display: its ten unique signal patterns and the four output digits.
+pub struct Entry {
+    pub line: usize,
+    pub signals: Vec<String>,
+    pub output: Vec<String>,
+}
+
+fn parse_line(line: &str, line_number: usize) -> Result<Entry> {
+    let mut iter = line.split("|");
+    let (signals, output) = match (iter.next(), iter.next(), iter.next()) {
+        (Some(signals), Some(output), None) => (signals, output),
+        _ => return Err(AocError::parse(line_number, line, "expected `signals | output`")),
+    };
+    let signals = signals
+        .split(" ")
+        .map(|x| x.to_string())
+        .filter(|x| !x.is_empty())
+        .collect();
+    let output = output
+        .split(" ")
+        .map(|x| x.to_string())
+        .filter(|x| !x.is_empty())
+        .collect();
+
+    return Ok(Entry { line: line_number, signals: signals, output: output });
+}
+
+/// Parses one `signals | output` entry per line.
+pub fn parse(input: &str) -> Result<Vec<Entry>> {
+    let mut entries: Vec<Entry> = Vec::new();
+    for (idx, line) in input.lines().enumerate() {
+        entries.push(parse_line(line, idx + 1)?);
+    }
+    return Ok(entries);
+}
+
+/// Decodes every display, returning how many output digits are 1, 4, 7 or 8
+/// and the sum of all the output values.
+fn decode_displays(entries: &[Entry]) -> Result<(u64, u64)> {
+    let mut part1_count = 0;
+    let mut part2_sum: u64 = 0;
+    let part1_to_count: HashSet<u8> = HashSet::from_iter(vec![1, 4, 7, 8]);
+
+    for entry in entries {
+        let mapping = identify_numbers(&entry.signals)
+            .ok_or_else(|| AocError::parse(entry.line, &entry.signals.join(" "), "the signals are not ten distinct digits"))?;
+        let mut result_value: u64 = 0;
+        for out_value in &entry.output {
+            let mapped = mapping
+                .identify_pattern(out_value)
+                .ok_or(AocError::parse(entry.line, out_value, "pattern not found"))?;
+
+            if part1_to_count.contains(mapped) {
+                // Part1: count the apparitions of 1, 4, 7, and 8
+                part1_count += 1;
+            }
+
+            result_value = result_value.checked_mul(10).and_then(|value| value.checked_add(*mapped as u64))
+                .ok_or_else(|| AocError::parse(entry.line, &entry.output.join(" "), "output value is too large"))?;
+        }
+        part2_sum = part2_sum.checked_add(result_value)
+            .ok_or_else(|| AocError::parse(entry.line, &entry.output.join(" "), "output values add up to too much"))?;
+    }
+
+    return Ok((part1_count, part2_sum));
+}
+
+/// How many output digits are 1, 4, 7 or 8.
+pub fn part1(entries: &[Entry]) -> Result<u64> {
+    return decode_displays(entries).map(|(count, _)| count);
+}
+
+/// The sum of the decoded output values.
+pub fn part2(entries: &[Entry]) -> Result<u64> {
+    return decode_displays(entries).map(|(_, sum)| sum);
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    fn day(&self) -> u8 {
+        return 8;
+    }
+
+    fn default_input(&self) -> &'static str {
+        return concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+    }
+
+    fn part1(&self, input: &str) -> Result<String> {
+        return parse(input).and_then(|entries| part1(&entries)).map(|answer| answer.to_string());
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        return parse(input).and_then(|entries| part2(&entries)).map(|answer| answer.to_string());
+    }
+}